@@ -116,12 +116,7 @@ impl IndexMut<usize> for Colorf {
 /// should have the same t range.
 fn plot_2d(spline: &bspline::BSpline<Point, f32>, colors: &bspline::BSpline<Colorf, f32>, plot: &mut [u8],
            plot_dim: (usize, usize), scale: (f32, f32), offset: (f32, f32), show_control_pts: bool) {
-    let step_size = 0.001;
-    let t_range = spline.knot_domain();
-    let steps = ((t_range.1 - t_range.0) / step_size) as usize;
-    for s in 0..steps + 1 {
-        let t = step_size * s as f32 + t_range.0;
-        let pt = spline.point(t);
+    for (t, pt) in spline.sample_iter(0.001) {
         let color = colors.point(t).to_srgb();
         let ix = ((pt.x + offset.0) * scale.0) as isize;
         let iy = ((pt.y + offset.1) * scale.1) as isize;
@@ -182,11 +177,11 @@ fn main() {
                       // Draw the e
                       Point::new(5.5, 0.8), Point::new(5.5, 0.8), Point::new(4.5, 1.3), Point::new(4.5, -0.3),
                       Point::new(6.5, -0.3)];
-    let knots = vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 5.0, 6.0, 7.0, 7.0,
-                     8.0, 9.0, 9.0, 9.0, 10.0, 10.0, 10.0, 11.0, 12.0, 13.0, 13.0,
-                     14.0, 14.0, 15.0, 16.0, 16.0, 17.0, 18.0, 18.0, 19.0, 19.0, 19.0,
-                     20.0, 20.0, 21.0, 21.0, 21.0, 22.0, 22.0, 22.0, 23.0, 24.0, 24.0,
-                     25.0, 25.0, 26.0, 27.0, 28.0, 28.0, 28.0, 28.0];
+    // A clamped cubic knot vector with repeated breakpoints for the corners of the letters
+    let corners = [(4.0, 2), (7.0, 2), (9.0, 3), (10.0, 3), (13.0, 2), (14.0, 2), (16.0, 2),
+                   (18.0, 2), (19.0, 3), (20.0, 2), (21.0, 3), (22.0, 3), (24.0, 2), (25.0, 2)];
+    let knots = corners.iter().fold(bspline::KnotVector::clamped(3, 31),
+                                    |knots, &(u, multiplicity)| knots.with_multiplicity(u, multiplicity));
 
     let colors = vec![Colorf::new(1.0, 0.0, 0.0), Colorf::new(0.0, 0.0, 1.0), Colorf::new(0.0, 1.0, 0.0)];
     let color_knots = vec![0.0, 0.0, 0.0, 28.0, 28.0, 28.0];
@@ -197,7 +192,7 @@ fn main() {
 
     let mut plot: Vec<_> = iter::repeat(255u8).take(plot_dim.0 * plot_dim.1 * 3).collect();
 
-    let spline = bspline::BSpline::new(3, points, knots);
+    let spline = bspline::BSpline::with_knot_vector(points, knots);
     let color_spline = bspline::BSpline::new(2, colors, color_knots);
 
     plot_2d(&spline, &color_spline, &mut plot[..], plot_dim, scale, offset, false);
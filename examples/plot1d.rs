@@ -6,12 +6,7 @@ use std::iter;
 /// Evaluate the B-spline and plot it to the image buffer passed
 fn plot_1d(spline: &bspline::BSpline<f32, f32>, plot: &mut [u8], plot_dim: (usize, usize), scale: (f32, f32),
            offset: (f32, f32)) {
-    let step_size = 0.001;
-    let t_range = spline.knot_domain();
-    let steps = ((t_range.1 - t_range.0) / step_size) as usize;
-    for s in 0..steps + 1 {
-        let t = step_size * s as f32 + t_range.0;
-        let y = spline.point(t);
+    for (t, y) in spline.sample_iter(0.001) {
         let ix = ((t + offset.0) * scale.0) as isize;
         let iy = ((y + offset.1) * scale.1) as isize;
         for y in iy - 1..iy + 1 {
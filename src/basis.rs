@@ -0,0 +1,168 @@
+use crate::{from_usize, knot_span, BSpline, Float, Interpolate, KnotVector};
+
+impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
+    /// Find the knot span containing `t`, the index `i` such that `knots[i] <= t < knots[i + 1]`
+    /// in the curve's knot vector. The `degree + 1` control points `i - degree..=i` are the
+    /// ones influencing the curve at `t`. Values of `t` at the end of the `knot_domain` or
+    /// outside of it are placed in the first or last non-empty span of the domain.
+    pub fn find_span(&self, t: F) -> usize {
+        // knot_span indexes a span by the knot ending it, while the span returned here is
+        // indexed by the knot starting it as in The NURBS Book
+        knot_span(&self.knots, self.degree, t) - 1
+    }
+    /// Compute the values of the `degree + 1` B-spline basis functions that are non-zero at
+    /// `t`, returning the knot span of `t` as found by `find_span` along with the values. The
+    /// value at index `r` is the basis function of control point `span - degree + r`, so the
+    /// point on the curve is the sum of those control points weighted by the values. The
+    /// values are non-negative and sum to one within the `knot_domain`.
+    pub fn basis_functions(&self, t: F) -> (usize, Vec<F>) {
+        basis_at(&self.knots, self.degree, t)
+    }
+    /// Compute the basis functions that are non-zero at `t` and their derivatives up to
+    /// `order`, returning the knot span of `t` as found by `find_span` along with the values.
+    /// Entry `[k][r]` is the `k`-th derivative of the basis function of control point
+    /// `span - degree + r`, where the derivatives above the degree are zero.
+    pub fn basis_derivatives(&self, t: F, order: usize) -> (usize, Vec<Vec<F>>) {
+        basis_derivatives_at(&self.knots, self.degree, t, order)
+    }
+}
+
+impl<F: Float> KnotVector<F> {
+    /// Find the knot span containing `t`, see `BSpline::find_span`.
+    pub fn find_span(&self, t: F) -> usize {
+        knot_span(&self.knots, self.degree, t) - 1
+    }
+    /// Compute the basis functions that are non-zero at `t` for a curve using these knots,
+    /// see `BSpline::basis_functions`.
+    pub fn basis_functions(&self, t: F) -> (usize, Vec<F>) {
+        basis_at(&self.knots, self.degree, t)
+    }
+    /// Compute the basis functions that are non-zero at `t` and their derivatives up to
+    /// `order` for a curve using these knots, see `BSpline::basis_derivatives`.
+    pub fn basis_derivatives(&self, t: F, order: usize) -> (usize, Vec<Vec<F>>) {
+        basis_derivatives_at(&self.knots, self.degree, t, order)
+    }
+}
+
+fn basis_at<F: Float>(knots: &[F], degree: usize, t: F) -> (usize, Vec<F>) {
+    let span = knot_span(knots, degree, t);
+    let mut basis = vec![F::zero(); degree + 1];
+    basis_functions(knots, degree, span, t, &mut basis);
+    (span - 1, basis)
+}
+
+fn basis_derivatives_at<F: Float>(
+    knots: &[F],
+    degree: usize,
+    t: F,
+    order: usize,
+) -> (usize, Vec<Vec<F>>) {
+    let span = knot_span(knots, degree, t);
+    (span - 1, basis_derivatives(knots, degree, span, t, order))
+}
+
+/// Compute the `degree + 1` basis functions that are non-zero at `t` into `out`, where `span`
+/// is the knot span of `t` as found by `knot_span`. `out[r]` is the value of the basis
+/// function for the control point `span - degree - 1 + r`.
+///
+/// This follows algorithm A2.2 from The NURBS Book, which builds up the basis functions of
+/// each degree from those of the degree below without computing any that are zero.
+pub(crate) fn basis_functions<F: Float>(
+    knots: &[F],
+    degree: usize,
+    span: usize,
+    t: F,
+    out: &mut [F],
+) {
+    let i = span - 1;
+    let mut left = vec![F::zero(); degree + 1];
+    let mut right = vec![F::zero(); degree + 1];
+    out[0] = F::one();
+    for j in 1..=degree {
+        left[j] = t - knots[i + 1 - j];
+        right[j] = knots[i + j] - t;
+        let mut saved = F::zero();
+        for r in 0..j {
+            let temp = out[r] / (right[r + 1] + left[j - r]);
+            out[r] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        out[j] = saved;
+    }
+}
+
+/// Compute the basis functions that are non-zero at `t` and their derivatives up to `order`,
+/// where `span` is the knot span of `t` as found by `knot_span`. Entry `[k][r]` of the
+/// result is the `k`-th derivative of the basis function for the control point
+/// `span - degree - 1 + r`, derivatives above the degree are zero.
+///
+/// This follows algorithm A2.3 from The NURBS Book, which reuses the triangular table of
+/// basis functions computed by A2.2 to compute the derivatives.
+pub(crate) fn basis_derivatives<F: Float>(
+    knots: &[F],
+    degree: usize,
+    span: usize,
+    t: F,
+    order: usize,
+) -> Vec<Vec<F>> {
+    let p = degree;
+    let i = span - 1;
+    let mut ders = vec![vec![F::zero(); p + 1]; order + 1];
+    // ndu stores the basis functions in its upper triangle and knot differences below it
+    let mut ndu = vec![vec![F::zero(); p + 1]; p + 1];
+    let mut left = vec![F::zero(); p + 1];
+    let mut right = vec![F::zero(); p + 1];
+    ndu[0][0] = F::one();
+    for j in 1..=p {
+        left[j] = t - knots[i + 1 - j];
+        right[j] = knots[i + j] - t;
+        let mut saved = F::zero();
+        for r in 0..j {
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+    for (j, d) in ders[0].iter_mut().enumerate() {
+        *d = ndu[j][p];
+    }
+
+    let n = order.min(p);
+    let mut a = vec![vec![F::zero(); p + 1]; 2];
+    for r in 0..=p {
+        let (mut s1, mut s2) = (0, 1);
+        a[0][0] = F::one();
+        for k in 1..=n {
+            let mut d = F::zero();
+            let rk = r as isize - k as isize;
+            let pk = p - k;
+            if rk >= 0 {
+                a[s2][0] = a[s1][0] / ndu[pk + 1][rk as usize];
+                d = a[s2][0] * ndu[rk as usize][pk];
+            }
+            let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+            let j2 = if r <= pk + 1 { k - 1 } else { p - r };
+            for j in j1..=j2 {
+                let rkj = (rk + j as isize) as usize;
+                a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][rkj];
+                d = d + a[s2][j] * ndu[rkj][pk];
+            }
+            if r <= pk {
+                a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                d = d + a[s2][k] * ndu[r][pk];
+            }
+            ders[k][r] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+    let mut factor = p;
+    for (k, row) in ders.iter_mut().enumerate().take(n + 1).skip(1) {
+        for d in row.iter_mut() {
+            *d = *d * from_usize(factor);
+        }
+        factor *= p - k;
+    }
+    ders
+}
@@ -0,0 +1,86 @@
+use crate::{with_scratch, BSpline, Float, Interpolate};
+
+/// A single polynomial segment of a B-spline curve in Bézier form, described by `degree + 1`
+/// control points and the parameter interval of the B-spline curve it covers.
+#[derive(Clone, Debug)]
+pub struct BezierSegment<T: Interpolate<F> + Copy, F: Float> {
+    /// The Bézier control points of the segment
+    control_points: Vec<T>,
+    /// The parameter interval of the original curve covered by the segment
+    interval: (F, F),
+}
+
+impl<T: Interpolate<F> + Copy, F: Float> BezierSegment<T, F> {
+    /// Create a Bézier segment covering `interval` of a curve's parameter domain. The degree
+    /// of the segment is one less than the number of control points.
+    pub fn new(control_points: Vec<T>, interval: (F, F)) -> BezierSegment<T, F> {
+        if control_points.is_empty() {
+            panic!("A Bézier segment needs at least one control point");
+        }
+        BezierSegment {
+            control_points,
+            interval,
+        }
+    }
+    /// Compute a point on the segment at `t` with de Casteljau's algorithm, where `t` is a
+    /// parameter value of the original curve within the segment's `interval`.
+    pub fn point(&self, t: F) -> T {
+        let s = (t - self.interval.0) / (self.interval.1 - self.interval.0);
+        with_scratch(&self.control_points, |tmp| {
+            for lvl in 1..tmp.len() {
+                for j in 0..tmp.len() - lvl {
+                    tmp[j] = tmp[j].interpolate(&tmp[j + 1], s);
+                }
+            }
+            tmp[0]
+        })
+    }
+    /// Get the degree of the segment.
+    pub fn degree(&self) -> usize {
+        self.control_points.len() - 1
+    }
+    /// Get the Bézier control points of the segment.
+    pub fn control_points(&self) -> &[T] {
+        &self.control_points
+    }
+    /// Get the parameter interval `(start, end)` of the original curve covered by the segment.
+    pub fn interval(&self) -> (F, F) {
+        self.interval
+    }
+}
+
+impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
+    /// Convert the curve into the sequence of Bézier segments making up each polynomial piece
+    /// of the curve over its `knot_domain`, in order of increasing `t`. Each segment has
+    /// `degree + 1` control points and the parameter interval of this curve it covers.
+    ///
+    /// The segments are found by inserting every distinct knot in the domain until it is
+    /// repeated `degree` times, at which point the control points of each knot span are
+    /// exactly the Bézier control points of the span.
+    pub fn to_bezier_segments(&self) -> Vec<BezierSegment<T, F>> {
+        let p = self.degree;
+        let (min, max) = self.knot_domain();
+        let mut new_knots = Vec::new();
+        let mut i = 0;
+        while i < self.knots.len() {
+            let k = self.knots[i];
+            let multiplicity = self.knots[i..].iter().take_while(|&&x| x == k).count();
+            if k >= min && k <= max {
+                for _ in multiplicity..p {
+                    new_knots.push(k);
+                }
+            }
+            i += multiplicity;
+        }
+        let refined = self.refine_knots(&new_knots).unwrap();
+
+        let n = refined.control_points.len() - 1;
+        (p..=n)
+            .filter(|&k| refined.knots[k] < refined.knots[k + 1])
+            .map(|k| BezierSegment {
+                control_points: refined.control_points[k - p..=k].to_vec(),
+                interval: (refined.knots[k], refined.knots[k + 1]),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{binomial, from_usize, BSpline, BSplineError, Float, InnerProduct, VectorSpace};
+
+impl<T: VectorSpace<F>, F: Float> BSpline<T, F> {
+    /// Raise the degree of the curve by `times`, returning a new curve of degree
+    /// `degree + times` that traces exactly the same shape. The multiplicity of each interior
+    /// knot is increased by `times` so the curve keeps the same continuity at the knots.
+    ///
+    /// The elevated curve has a clamped knot vector, if this curve's knot vector isn't
+    /// clamped it's first clamped to the `knot_domain` as done by `subcurve`.
+    pub fn elevate_degree(&self, times: usize) -> BSpline<T, F> {
+        if times == 0 {
+            return self.clone();
+        }
+        if self.degree == 0 {
+            return self.elevate_piecewise_constant(times);
+        }
+        if !self.is_clamped() {
+            let (min, max) = self.knot_domain();
+            return self.subcurve(min, max).elevate_degree(times);
+        }
+
+        // This follows algorithm A5.9 from The NURBS Book, using the same indexing: there are
+        // n + 1 control points and m + 1 knots. Each Bézier segment of the curve is extracted
+        // by knot insertion, degree elevated and then the inserted knots are removed again.
+        let t = times;
+        let p = self.degree;
+        let n = self.control_points.len() - 1;
+        let m = n + p + 1;
+        let ph = p + t;
+        let u = &self.knots;
+        let pw = &self.control_points;
+
+        // Coefficients for elevating the degree of the Bézier segments
+        let bezalfs: Vec<Vec<F>> = (0..=ph)
+            .map(|i| {
+                let inv = F::one() / from_usize(binomial(ph, i));
+                (0..=p)
+                    .map(|j| match i.checked_sub(j) {
+                        Some(k) => inv * from_usize(binomial(p, j) * binomial(t, k)),
+                        None => F::zero(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let distinct_interior = (p + 1..=n).filter(|&i| u[i] != u[i - 1]).count();
+        let mut qw = vec![pw[0]; n + 1 + t * (distinct_interior + 1)];
+        let mut uh = vec![u[0]; m + 1 + t * (distinct_interior + 2)];
+        let mut bpts = pw[..=p].to_vec();
+        let mut ebpts = vec![pw[0]; ph + 1];
+        let mut next_bpts = vec![pw[0]; p.max(1)];
+        let mut alfs = vec![F::zero(); p.max(1)];
+
+        let mut mh = ph;
+        let mut kind = ph + 1;
+        let mut r: isize = -1;
+        let mut a = p;
+        let mut b = p + 1;
+        let mut cind = 1;
+        let mut ua = u[0];
+        while b < m {
+            let i = b;
+            while b < m && u[b] == u[b + 1] {
+                b += 1;
+            }
+            let mul = b - i + 1;
+            mh += mul + t;
+            let ub = u[b];
+            let oldr = r;
+            r = p as isize - mul as isize;
+            // After a knot repeated degree + 1 times the curve is discontinuous, so the first
+            // point of this segment isn't shared with the end of the last one and is kept
+            let lbz = if oldr > 0 {
+                (oldr as usize + 2) / 2
+            } else if oldr < 0 && a != p {
+                0
+            } else {
+                1
+            };
+            let rbz = if r > 0 {
+                ph - (r as usize).div_ceil(2)
+            } else {
+                ph
+            };
+            // Insert the knot ub r times to get the Bézier segment
+            if r > 0 {
+                let r = r as usize;
+                let numer = ub - ua;
+                for k in (mul + 1..=p).rev() {
+                    alfs[k - mul - 1] = numer / (u[a + k] - ua);
+                }
+                for j in 1..=r {
+                    let save = r - j;
+                    let s = mul + j;
+                    for k in (s..=p).rev() {
+                        bpts[k] = bpts[k - 1].interpolate(&bpts[k], alfs[k - s]);
+                    }
+                    next_bpts[save] = bpts[p];
+                }
+            }
+            // Elevate the degree of the Bézier segment
+            for (i, e) in ebpts.iter_mut().enumerate().skip(lbz) {
+                let j0 = i.saturating_sub(t);
+                *e = bpts[j0].scale(bezalfs[i][j0]);
+                for j in j0 + 1..=p.min(i) {
+                    *e = e.sum(&bpts[j].scale(bezalfs[i][j]));
+                }
+            }
+            // Remove the knot ua oldr times
+            if oldr > 1 {
+                let kind_i = kind as isize;
+                let den = ub - ua;
+                let bet = (ub - uh[kind - 1]) / den;
+                for tr in 1..oldr {
+                    let mut i = kind_i - 1 - tr;
+                    let mut j = kind_i + tr - 1;
+                    let mut kj = j - kind_i + 1;
+                    while j - i > tr {
+                        if i < cind as isize {
+                            let iu = i as usize;
+                            let alf = (ub - uh[iu]) / (ua - uh[iu]);
+                            qw[iu] = qw[iu - 1].interpolate(&qw[iu], alf);
+                        }
+                        if j >= lbz as isize {
+                            let k = kj as usize;
+                            if j - tr <= kind_i - ph as isize + oldr {
+                                let gam = (ub - uh[(j - tr) as usize]) / den;
+                                ebpts[k] = ebpts[k + 1].interpolate(&ebpts[k], gam);
+                            } else {
+                                ebpts[k] = ebpts[k + 1].interpolate(&ebpts[k], bet);
+                            }
+                        }
+                        i += 1;
+                        j -= 1;
+                        kj -= 1;
+                    }
+                }
+            }
+            // Load the knot ua
+            if a != p {
+                for _ in 0..(ph as isize - oldr) {
+                    uh[kind] = ua;
+                    kind += 1;
+                }
+            }
+            // Load the control points
+            for e in ebpts.iter().take(rbz + 1).skip(lbz) {
+                qw[cind] = *e;
+                cind += 1;
+            }
+            if b < m {
+                // Set up the next Bézier segment
+                let r = r.max(0) as usize;
+                bpts[..r].copy_from_slice(&next_bpts[..r]);
+                bpts[r..].copy_from_slice(&pw[b - p + r..=b]);
+                a = b;
+                b += 1;
+                ua = ub;
+            } else {
+                for k in uh.iter_mut().skip(kind).take(ph + 1) {
+                    *k = ub;
+                }
+            }
+        }
+        let nh = mh - ph - 1;
+        qw.truncate(nh + 1);
+        uh.truncate(mh + 1);
+        BSpline {
+            degree: ph,
+            control_points: qw,
+            knots: uh,
+        }
+    }
+    /// Elevate the degree of a degree 0 curve, which is discontinuous at every knot. Each
+    /// constant segment becomes a Bézier segment with all control points equal to the
+    /// segment's value.
+    fn elevate_piecewise_constant(&self, times: usize) -> BSpline<T, F> {
+        let mut control_points = Vec::new();
+        let mut knots = vec![self.knots[0]; times + 1];
+        for (i, p) in self.control_points.iter().enumerate() {
+            if self.knots[i] < self.knots[i + 1] {
+                control_points.extend(std::iter::repeat_n(*p, times + 1));
+                knots.extend(std::iter::repeat_n(self.knots[i + 1], times + 1));
+            }
+        }
+        BSpline {
+            degree: times,
+            control_points,
+            knots,
+        }
+    }
+}
+
+impl<T: InnerProduct<F>, F: Float> BSpline<T, F> {
+    /// Approximate the curve with a curve of one lower degree, returning the new curve along
+    /// with its deviation from this curve. The deviation is an upper bound on the distance
+    /// between the two curves at any parameter value, and if it's greater than `tolerance`
+    /// an error is returned instead. To lower the degree by more than one call this again on
+    /// the result, the deviations of each step add up.
+    ///
+    /// The reduced curve has a clamped knot vector over this curve's `knot_domain` with the
+    /// same breakpoints, where each interior knot is repeated one time less than in this
+    /// curve, but at least once. This keeps the continuity of the curve at its knots, up to
+    /// the `degree - 2` continuous derivatives a curve of the lower degree can have. Knots
+    /// that can't be removed within the tolerance are left repeated, so the reduced curve is
+    /// less smooth there. The curve must be at least degree 2.
+    pub fn reduce_degree(&self, tolerance: F) -> Result<(BSpline<T, F>, F), BSplineError> {
+        let p = self.degree;
+        if p < 2 {
+            return Err(BSplineError::DegreeTooLow { degree: p, min: 2 });
+        }
+        // Like algorithm A5.11 from The NURBS Book each Bézier segment is reduced on its own,
+        // joining the segments with knots of multiplicity `degree - 1`, and then the knots are
+        // removed again down to their final multiplicity within the remaining tolerance.
+        let (segmented, deviation) = self.reduce_segments();
+        if deviation > tolerance {
+            return Err(BSplineError::ToleranceExceeded);
+        }
+        let (min, max) = self.knot_domain();
+        let mut breakpoints: Vec<F> = self
+            .knots
+            .iter()
+            .cloned()
+            .filter(|&k| k > min && k < max)
+            .collect();
+        breakpoints.dedup();
+        // The knots removed are measured against the segmented curve, so the total deviation
+        // is bounded by the sum of the two steps
+        let budget = tolerance - deviation;
+        let mut curve = segmented.clone();
+        let mut removed = F::zero();
+        for u in breakpoints {
+            let target = self.knots.iter().filter(|&&k| k == u).count().max(2) - 1;
+            let mut multiplicity = curve.knots.iter().filter(|&&k| k == u).count();
+            while multiplicity > target {
+                let candidate = curve.remove_knot_unchecked(u, 1, multiplicity);
+                let d = candidate.deviation_from(&segmented);
+                if d > budget {
+                    break;
+                }
+                curve = candidate;
+                removed = d;
+                multiplicity -= 1;
+            }
+        }
+        Ok((curve, deviation + removed))
+    }
+    /// Reduce the degree of each Bézier segment of the curve by one, returning the curve made
+    /// of the reduced segments along with its deviation from this curve. The segments are
+    /// joined by knots repeated `degree - 1` times where this curve is continuous, keeping
+    /// the ends of each segment, and `degree` times where it isn't.
+    fn reduce_segments(&self) -> (BSpline<T, F>, F) {
+        let p = self.degree;
+        let q = p - 1;
+        let segments = self.to_bezier_segments();
+        let mut control_points = Vec::with_capacity(segments.len() * q + 1);
+        let mut knots = vec![segments[0].interval().0; q + 1];
+        let mut deviation = F::zero();
+        for (i, segment) in segments.iter().enumerate() {
+            let points = segment.control_points();
+            let reduced = reduce_bezier(points);
+            // The deviation of the segment is bounded by the distance between the control
+            // points of the original and the reduced segment raised back to degree p
+            for (j, pt) in points.iter().enumerate() {
+                let a = from_usize::<F>(j) / from_usize(p);
+                let elevated = if j == 0 {
+                    reduced[0]
+                } else if j == p {
+                    reduced[q]
+                } else {
+                    reduced[j].interpolate(&reduced[j - 1], a)
+                };
+                let d = elevated.difference(pt).norm();
+                if d > deviation {
+                    deviation = d;
+                }
+            }
+            // Shared end points are only stored once where the curve is continuous
+            let start = segment.interval().0;
+            let continuous = self.knots.iter().filter(|&&k| k == start).count() <= p;
+            if i == 0 || !continuous {
+                if i != 0 {
+                    knots.push(start);
+                }
+                control_points.extend_from_slice(&reduced);
+            } else {
+                control_points.extend_from_slice(&reduced[1..]);
+            }
+            let end = segment.interval().1;
+            knots.extend(std::iter::repeat_n(end, q));
+        }
+        knots.push(segments[segments.len() - 1].interval().1);
+        let curve = BSpline {
+            degree: q,
+            control_points,
+            knots,
+        };
+        (curve, deviation)
+    }
+}
+
+/// Reduce the degree of the Bézier curve with control points `points` by one, following
+/// equations 5.41 to 5.45 of The NURBS Book. The new control points are found by solving the
+/// degree elevation equations from both ends towards the middle, which keeps the end points.
+fn reduce_bezier<T: VectorSpace<F>, F: Float>(points: &[T]) -> Vec<T> {
+    let p = points.len() - 1;
+    let r = (p - 1) / 2;
+    let alpha = |i: usize| from_usize::<F>(i) / from_usize(p);
+    let mut reduced = vec![points[0]; p];
+    reduced[p - 1] = points[p];
+    // Solve for the points from the start, P_i = a_i Q_(i - 1) + (1 - a_i) Q_i
+    let forward = |reduced: &[T], i: usize| {
+        points[i]
+            .difference(&reduced[i - 1].scale(alpha(i)))
+            .scale(F::one() / (F::one() - alpha(i)))
+    };
+    // Solve for the points from the end, P_(i + 1) = a_(i + 1) Q_i + (1 - a_(i + 1)) Q_(i + 1)
+    let backward = |reduced: &[T], i: usize| {
+        points[i + 1]
+            .difference(&reduced[i + 1].scale(F::one() - alpha(i + 1)))
+            .scale(F::one() / alpha(i + 1))
+    };
+    let even = p.is_multiple_of(2);
+    let forward_end = if even { r + 1 } else { r };
+    for i in 1..forward_end {
+        reduced[i] = forward(&reduced, i);
+    }
+    for i in (r + 1..p - 1).rev() {
+        reduced[i] = backward(&reduced, i);
+    }
+    // For odd degrees the middle point is found from both ends and averaged
+    if !even {
+        let left = forward(&reduced, r);
+        let right = backward(&reduced, r);
+        reduced[r] = left.interpolate(&right, F::one() / from_usize(2));
+    }
+    reduced
+}
@@ -0,0 +1,173 @@
+use std::error::Error;
+use std::fmt;
+
+/// The errors that can occur when validating the parameters of a B-spline curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BSplineError {
+    /// The curve needs at least `degree + 1` control points but only `found` were given.
+    TooFewControlPoints { degree: usize, found: usize },
+    /// The knot vector must have `control_points.len() + degree + 1` knots.
+    InvalidKnotCount { expected: usize, found: usize },
+    /// The knot at `index` is NaN or infinite.
+    NonFiniteKnot { index: usize },
+    /// The knot at `index` is less than the knot before it.
+    UnsortedKnots { index: usize },
+    /// The knot value starting at `index` is repeated `multiplicity` times, which is more
+    /// than the `max` of `degree + 1` repetitions allowed.
+    KnotMultiplicityTooHigh {
+        index: usize,
+        multiplicity: usize,
+        max: usize,
+    },
+    /// The knots bounding the curve's domain are equal, so there are no values of `t`
+    /// the curve can be evaluated at.
+    EmptyKnotDomain,
+    /// A rational curve needs one weight per control point.
+    InvalidWeightCount { expected: usize, found: usize },
+    /// The weight at `index` is not a finite positive value.
+    InvalidWeight { index: usize },
+    /// A parameter value passed to the curve is outside of its knot domain.
+    ParameterOutOfDomain,
+    /// The operation isn't supported for curves of `degree`, at least degree `min` is needed.
+    DegreeTooLow { degree: usize, min: usize },
+    /// The curve can't be approximated within the requested tolerance.
+    ToleranceExceeded,
+    /// The linear system solved to fit a curve to data is singular, so the data doesn't
+    /// determine a unique curve. This happens when data points share a parameter value or
+    /// there are too few data points for the number of control points.
+    SingularSystem,
+    /// Interpolation needs one optional derivative per data point.
+    InvalidDerivativeCount { expected: usize, found: usize },
+    /// A periodic curve must start and end at the same point, with the same derivative if
+    /// one is given at both ends.
+    PeriodicMismatch,
+    /// A not-a-knot end condition removes the knot at data point `index`, so the curve can't
+    /// also be given a derivative there.
+    NotAKnotDerivative { index: usize },
+    /// The smoothing parameter for fitting a curve is not a finite, non-negative value.
+    InvalidSmoothing,
+    /// A knot can't be removed `times` times as it's only repeated `multiplicity` times.
+    KnotMultiplicityTooLow { multiplicity: usize, times: usize },
+    /// Row `row` of a surface's control point grid has `found` control points, but the rows
+    /// must all have the `expected` length of the first row. The rows of a volume's control
+    /// lattice are counted through the lattice slab by slab.
+    InvalidGridRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// Slab `slab` of a volume's control lattice has `found` rows, but the slabs must all have
+    /// the `expected` number of rows of the first slab.
+    InvalidLatticeSlab {
+        slab: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for BSplineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BSplineError::TooFewControlPoints { degree, found } => write!(
+                f,
+                "Too few control points for curve, a degree {} curve needs at least {} but got {}",
+                degree,
+                degree + 1,
+                found
+            ),
+            BSplineError::InvalidKnotCount { expected, found } => write!(
+                f,
+                "Invalid number of knots, got {}, expected {}",
+                found, expected
+            ),
+            BSplineError::NonFiniteKnot { index } => {
+                write!(f, "Knot {} is not a finite value", index)
+            }
+            BSplineError::UnsortedKnots { index } => write!(
+                f,
+                "Knots must be in non-decreasing order, knot {} is less than knot {}",
+                index,
+                index - 1
+            ),
+            BSplineError::KnotMultiplicityTooHigh {
+                index,
+                multiplicity,
+                max,
+            } => write!(
+                f,
+                "Knot {} is repeated {} times, at most {} repetitions are allowed",
+                index, multiplicity, max
+            ),
+            BSplineError::EmptyKnotDomain => write!(f, "The curve's knot domain is empty"),
+            BSplineError::InvalidWeightCount { expected, found } => write!(
+                f,
+                "Invalid number of weights, got {}, expected {}",
+                found, expected
+            ),
+            BSplineError::InvalidWeight { index } => {
+                write!(f, "Weight {} is not a finite positive value", index)
+            }
+            BSplineError::ParameterOutOfDomain => {
+                write!(f, "The parameter is outside of the curve's knot domain")
+            }
+            BSplineError::DegreeTooLow { degree, min } => write!(
+                f,
+                "Unsupported curve degree {}, the degree must be at least {}",
+                degree, min
+            ),
+            BSplineError::ToleranceExceeded => {
+                write!(f, "The curve can't be approximated within the tolerance")
+            }
+            BSplineError::SingularSystem => {
+                write!(f, "The data doesn't determine a unique curve")
+            }
+            BSplineError::InvalidDerivativeCount { expected, found } => write!(
+                f,
+                "Invalid number of derivatives, got {}, expected {}",
+                found, expected
+            ),
+            BSplineError::PeriodicMismatch => {
+                write!(f, "The ends of the periodic curve don't match")
+            }
+            BSplineError::NotAKnotDerivative { index } => write!(
+                f,
+                "Data point {} can't have a derivative with a not-a-knot end condition",
+                index
+            ),
+            BSplineError::InvalidSmoothing => {
+                write!(
+                    f,
+                    "The smoothing parameter is not a finite non-negative value"
+                )
+            }
+            BSplineError::KnotMultiplicityTooLow {
+                multiplicity,
+                times,
+            } => write!(
+                f,
+                "Can't remove a knot {} times, it's only repeated {} times",
+                times, multiplicity
+            ),
+            BSplineError::InvalidGridRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Row {} of the control point grid has {} points, expected {}",
+                row, found, expected
+            ),
+            BSplineError::InvalidLatticeSlab {
+                slab,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Slab {} of the control point lattice has {} rows, expected {}",
+                slab, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for BSplineError {}
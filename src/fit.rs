@@ -0,0 +1,639 @@
+use crate::basis::{basis_derivatives, basis_functions};
+use crate::linalg::BandMatrix;
+use crate::nurbs::validate_weights;
+use crate::quadrature::gauss_legendre;
+use crate::{
+    check_knot_multiplicity, from_usize, knot_span, to_usize, BSpline, BSplineError, Float,
+    InnerProduct, KnotVector, VectorSpace,
+};
+
+/// How the parameter values of the data points are chosen when fitting a curve to them,
+/// which controls how the curve is shaped between the points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parameterization {
+    /// The parameters are evenly spaced, ignoring the distance between the points. This can
+    /// make the curve wiggle or loop where the points are unevenly spaced.
+    Uniform,
+    /// The parameters are spaced by the distance between consecutive points, which works
+    /// well for most data.
+    ChordLength,
+    /// The parameters are spaced by the square root of the distance between consecutive
+    /// points, which gives tighter curves around sharp turns than `ChordLength`.
+    Centripetal,
+}
+
+/// Extra options for least squares fitting, by default all samples are weighted equally and
+/// the curve isn't required to pass through any of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FitOptions<F: Float> {
+    /// How much each sample contributes to the error being minimized
+    weights: Option<Vec<F>>,
+    /// Whether the curve must pass through the first and last samples
+    fix_endpoints: bool,
+}
+
+impl<F: Float> FitOptions<F> {
+    /// Create the default options, with equally weighted samples and free endpoints.
+    pub fn new() -> FitOptions<F> {
+        FitOptions {
+            weights: None,
+            fix_endpoints: false,
+        }
+    }
+    /// Weight the squared error of each sample by the corresponding entry of `weights`, so
+    /// samples with higher weights are fit more closely. There must be one finite, positive
+    /// weight per sample, otherwise fitting returns an error.
+    pub fn with_weights(mut self, weights: Vec<F>) -> FitOptions<F> {
+        self.weights = Some(weights);
+        self
+    }
+    /// Require the curve to start and end exactly at the samples with the lowest and highest
+    /// parameter values, instead of fitting them in the least squares sense like the others.
+    pub fn with_fixed_endpoints(mut self, fix_endpoints: bool) -> FitOptions<F> {
+        self.fix_endpoints = fix_endpoints;
+        self
+    }
+}
+
+impl<F: Float> Default for FitOptions<F> {
+    fn default() -> FitOptions<F> {
+        FitOptions::new()
+    }
+}
+
+/// How much a smoothing fit trades closeness to the data for smoothness of the curve, see
+/// `BSpline::fit_smoothing`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Smoothing<F> {
+    /// Use the given smoothing parameter λ, which must be finite and non-negative. A λ of
+    /// zero gives a plain least squares fit and larger values give smoother curves, tending
+    /// towards the straight line fit to the data.
+    Fixed(F),
+    /// Choose λ automatically by minimizing the generalized cross-validation score, which
+    /// estimates how well the curve would predict data left out of the fit.
+    GeneralizedCrossValidation,
+}
+
+impl<T: VectorSpace<F>, F: Float> BSpline<T, F> {
+    /// Find the clamped B-spline curve of `degree` with `n_control_points` control points
+    /// that best approximates the `samples` in the least squares sense, where each sample is
+    /// a parameter value and the point the curve should be near at that parameter. The curve
+    /// minimizes the sum of the squared distances between the samples and the curve, and its
+    /// knot domain spans the parameters of the samples.
+    ///
+    /// The interior knots are placed so each knot span contains parameters of some samples,
+    /// following equation 9.69 of The NURBS Book, which keeps the system for the control
+    /// points well conditioned. Use `fit_least_squares_with` to weight the samples or fix the
+    /// ends of the curve.
+    ///
+    /// There must be more control points than the degree and at least as many samples as
+    /// control points, otherwise an error is returned. `BSplineError::SingularSystem` is
+    /// also returned if so many samples share a parameter value that a knot would be repeated
+    /// more than `degree + 1` times.
+    pub fn fit_least_squares(
+        degree: usize,
+        n_control_points: usize,
+        samples: &[(F, T)],
+    ) -> Result<BSpline<T, F>, BSplineError> {
+        BSpline::fit_least_squares_with(degree, n_control_points, samples, &FitOptions::new())
+    }
+    /// Find the clamped B-spline curve that best approximates the `samples` in the least
+    /// squares sense as with `fit_least_squares`, using the sample weights and endpoint
+    /// constraints in `options`.
+    pub fn fit_least_squares_with(
+        degree: usize,
+        n_control_points: usize,
+        samples: &[(F, T)],
+        options: &FitOptions<F>,
+    ) -> Result<BSpline<T, F>, BSplineError> {
+        let fit = LeastSquares::new(degree, n_control_points, samples, options)?;
+        if samples.len() < n_control_points {
+            return Err(BSplineError::SingularSystem);
+        }
+        let knots = fit_knots(&fit.params, degree, n_control_points);
+        // Knots are only repeated too often when samples share a parameter value
+        check_knot_multiplicity(&knots, degree).map_err(|_| BSplineError::SingularSystem)?;
+        let (matrix, rhs) = fit.normal_equations(&knots);
+        fit.solve(knots, matrix, rhs)
+    }
+}
+
+impl<T: InnerProduct<F>, F: Float> BSpline<T, F> {
+    /// Find the smoothing B-spline curve of `degree` with `n_control_points` control points
+    /// for the `samples`, returning the curve and the smoothing parameter λ used. Each sample
+    /// is a parameter value and the point the curve should be near at that parameter. The
+    /// curve minimizes the sum of the squared distances to the samples plus λ times the
+    /// integral of the squared second derivative of the curve over its knot domain, so noise
+    /// in the data is smoothed out instead of being fit.
+    ///
+    /// The knots are evenly spaced over the parameters of the samples. As the penalty
+    /// controls the smoothness of the curve the number of control points doesn't need to be
+    /// tuned, it should just be large enough to follow the features of the data and can be
+    /// more than the number of samples. The sample weights and endpoint constraints in
+    /// `options` are used as in `fit_least_squares_with`.
+    ///
+    /// The degree must be at least 2 for the curve to have a second derivative and there
+    /// must be more control points than the degree, otherwise an error is returned.
+    pub fn fit_smoothing(
+        degree: usize,
+        n_control_points: usize,
+        samples: &[(F, T)],
+        smoothing: Smoothing<F>,
+        options: &FitOptions<F>,
+    ) -> Result<(BSpline<T, F>, F), BSplineError> {
+        if degree < 2 {
+            return Err(BSplineError::DegreeTooLow { degree, min: 2 });
+        }
+        let fit = LeastSquares::new(degree, n_control_points, samples, options)?;
+        let (min, max) = (fit.params[0], fit.params[fit.params.len() - 1]);
+        let knots: Vec<F> = KnotVector::clamped(degree, n_control_points)
+            .rescale(min, max)
+            .into();
+        let (data, rhs) = fit.normal_equations(&knots);
+        let (penalty, penalty_rhs) = fit.penalty(&knots);
+        let lambda = match smoothing {
+            Smoothing::Fixed(lambda) => {
+                if !lambda.is_finite() || lambda < F::zero() {
+                    return Err(BSplineError::InvalidSmoothing);
+                }
+                lambda
+            }
+            Smoothing::GeneralizedCrossValidation => {
+                fit.choose_smoothing(&knots, &data, &rhs, &penalty, &penalty_rhs)?
+            }
+        };
+        let mut matrix = data;
+        matrix.add_scaled(&penalty, lambda);
+        let rhs = penalized_rhs(&rhs, &penalty_rhs, lambda);
+        Ok((fit.solve(knots, matrix, rhs)?, lambda))
+    }
+
+    /// Find the B-spline curve of `degree` passing through all the `points` in order, with
+    /// one control point per data point. The curve is parameterized over `[0, 1]` with the
+    /// parameters of the points chosen by `parameterization`, and the knots are placed by
+    /// averaging the parameters so the linear system for the control points is well
+    /// conditioned, as described in section 9.2.1 of The NURBS Book.
+    ///
+    /// The degree must be at least 1 and there must be more points than the degree,
+    /// otherwise an error is returned. An error is also returned if consecutive points are
+    /// equal when using `ChordLength` or `Centripetal` parameterization, as the points would
+    /// share a parameter value.
+    pub fn interpolate_points(
+        degree: usize,
+        points: &[T],
+        parameterization: Parameterization,
+    ) -> Result<BSpline<T, F>, BSplineError> {
+        if degree == 0 {
+            return Err(BSplineError::DegreeTooLow { degree, min: 1 });
+        }
+        if points.len() <= degree {
+            return Err(BSplineError::TooFewControlPoints {
+                degree,
+                found: points.len(),
+            });
+        }
+        let params = parameterize(points, parameterization)?;
+        let knots = averaged_knots(&params, degree);
+        let mut control_points = points.to_vec();
+        collocation_matrix(&knots, degree, &params).solve(&mut control_points)?;
+        Ok(BSpline {
+            degree,
+            control_points,
+            knots,
+        })
+    }
+}
+
+/// Compute the parameter values in `[0, 1]` of the `points` with `parameterization`,
+/// returning an error if two points would have the same parameter.
+pub(crate) fn parameterize<T: InnerProduct<F>, F: Float>(
+    points: &[T],
+    parameterization: Parameterization,
+) -> Result<Vec<F>, BSplineError> {
+    let n = points.len() - 1;
+    if parameterization == Parameterization::Uniform {
+        return Ok((0..=n)
+            .map(|k| from_usize::<F>(k) / from_usize(n))
+            .collect());
+    }
+    let mut params = Vec::with_capacity(n + 1);
+    params.push(F::zero());
+    for k in 1..=n {
+        let mut d = points[k].difference(&points[k - 1]).norm();
+        if parameterization == Parameterization::Centripetal {
+            d = d.sqrt();
+        }
+        if d <= F::zero() || !d.is_finite() {
+            return Err(BSplineError::SingularSystem);
+        }
+        params.push(params[k - 1] + d);
+    }
+    let total = params[n];
+    for u in params.iter_mut() {
+        *u = *u / total;
+    }
+    params[n] = F::one();
+    Ok(params)
+}
+
+/// Place the knots of a clamped curve of `degree` with one control point per parameter
+/// value by averaging `degree` consecutive parameters, equation 9.8 of The NURBS Book.
+fn averaged_knots<F: Float>(params: &[F], degree: usize) -> Vec<F> {
+    let n = params.len() - 1;
+    let mut knots = vec![F::zero(); degree + 1];
+    for j in 1..=n - degree {
+        let sum = params[j..j + degree]
+            .iter()
+            .fold(F::zero(), |sum, &u| sum + u);
+        knots.push(sum / from_usize(degree));
+    }
+    knots.extend(std::iter::repeat_n(F::one(), degree + 1));
+    knots
+}
+
+/// Build the matrix of the basis functions of the curve with `knots` evaluated at each of
+/// the `params`, with one row per parameter and one column per control point.
+fn collocation_matrix<F: Float>(knots: &[F], degree: usize, params: &[F]) -> BandMatrix<F> {
+    let spans: Vec<usize> = params
+        .iter()
+        .map(|&u| knot_span(knots, degree, u))
+        .collect();
+    // The first column of each row is span - degree - 1, which is used to find the band
+    let lower = spans
+        .iter()
+        .enumerate()
+        .map(|(k, &s)| (k + degree + 1).saturating_sub(s))
+        .max()
+        .unwrap_or(0);
+    let upper = spans
+        .iter()
+        .enumerate()
+        .map(|(k, &s)| (s - 1).saturating_sub(k))
+        .max()
+        .unwrap_or(0);
+    let mut matrix = BandMatrix::new(params.len(), lower, upper);
+    let mut basis = vec![F::zero(); degree + 1];
+    for (k, (&u, &s)) in params.iter().zip(spans.iter()).enumerate() {
+        basis_functions(knots, degree, s, u, &mut basis);
+        for (r, &b) in basis.iter().enumerate() {
+            if b != F::zero() {
+                matrix.add(k, s - degree - 1 + r, b);
+            }
+        }
+    }
+    matrix
+}
+
+/// The data for a least squares fit of a clamped curve to samples, sorted by parameter.
+struct LeastSquares<T, F> {
+    degree: usize,
+    n_control_points: usize,
+    /// The parameters of the samples in increasing order
+    params: Vec<F>,
+    /// The sample points matching `params`
+    points: Vec<T>,
+    /// The weight of each sample
+    weights: Vec<F>,
+    /// Whether the first and last control points are fixed to the first and last samples
+    fix_endpoints: bool,
+}
+
+impl<T: VectorSpace<F>, F: Float> LeastSquares<T, F> {
+    /// Validate the fit parameters and sort the samples by their parameter values
+    fn new(
+        degree: usize,
+        n_control_points: usize,
+        samples: &[(F, T)],
+        options: &FitOptions<F>,
+    ) -> Result<LeastSquares<T, F>, BSplineError> {
+        if n_control_points <= degree {
+            return Err(BSplineError::TooFewControlPoints {
+                degree,
+                found: n_control_points,
+            });
+        }
+        if options.fix_endpoints && n_control_points < 2 {
+            return Err(BSplineError::TooFewControlPoints {
+                degree,
+                found: n_control_points,
+            });
+        }
+        if let Some(weights) = &options.weights {
+            validate_weights(weights, samples.len())?;
+        }
+        if samples.iter().any(|(t, _)| !t.is_finite()) {
+            return Err(BSplineError::ParameterOutOfDomain);
+        }
+        let mut order: Vec<usize> = (0..samples.len()).collect();
+        order.sort_by(|&a, &b| samples[a].0.partial_cmp(&samples[b].0).unwrap());
+        if samples.is_empty() || samples[order[0]].0 == samples[order[order.len() - 1]].0 {
+            return Err(BSplineError::EmptyKnotDomain);
+        }
+        Ok(LeastSquares {
+            degree,
+            n_control_points,
+            params: order.iter().map(|&i| samples[i].0).collect(),
+            points: order.iter().map(|&i| samples[i].1).collect(),
+            weights: match &options.weights {
+                Some(w) => order.iter().map(|&i| w[i]).collect(),
+                None => vec![F::one(); samples.len()],
+            },
+            fix_endpoints: options.fix_endpoints,
+        })
+    }
+    /// The index of the first control point solved for, the ones before it are fixed
+    fn first_free(&self) -> usize {
+        if self.fix_endpoints {
+            1
+        } else {
+            0
+        }
+    }
+    /// The number of control points solved for
+    fn free_count(&self) -> usize {
+        self.n_control_points - 2 * self.first_free()
+    }
+    /// Build the normal equations `N^T W N P = N^T W Q` for the free control points of the
+    /// curve with `knots`, where `N` is the matrix of basis functions at the sample
+    /// parameters, `W` the sample weights and `Q` the sample points. The contribution of the
+    /// fixed control points is moved to the right hand side.
+    fn normal_equations(&self, knots: &[F]) -> (BandMatrix<F>, Vec<T>) {
+        let p = self.degree;
+        let first = self.first_free();
+        let free = self.free_count();
+        let last_point = self.points[self.points.len() - 1];
+        let mut matrix = BandMatrix::new(free, p, p);
+        let mut rhs = vec![self.points[0].scale(F::zero()); free];
+        let mut basis = vec![F::zero(); p + 1];
+        for ((&u, q), &w) in self
+            .params
+            .iter()
+            .zip(self.points.iter())
+            .zip(&self.weights)
+        {
+            let span = knot_span(knots, p, u);
+            basis_functions(knots, p, span, u, &mut basis);
+            let start = span - p - 1;
+            // Move the fixed control points over to the data
+            let mut q = *q;
+            for (r, &b) in basis.iter().enumerate() {
+                let col = start + r;
+                if col < first {
+                    q = q.difference(&self.points[0].scale(b));
+                } else if col >= first + free {
+                    q = q.difference(&last_point.scale(b));
+                }
+            }
+            for (r, &b) in basis.iter().enumerate() {
+                let col = start + r;
+                if col < first || col >= first + free || b == F::zero() {
+                    continue;
+                }
+                rhs[col - first] = rhs[col - first].sum(&q.scale(w * b));
+                for (s, &c) in basis.iter().enumerate() {
+                    let col2 = start + s;
+                    if col2 >= first && col2 < first + free && c != F::zero() {
+                        matrix.add(col - first, col2 - first, w * b * c);
+                    }
+                }
+            }
+        }
+        (matrix, rhs)
+    }
+    /// Solve the normal equations for the free control points and build the curve
+    fn solve(
+        &self,
+        knots: Vec<F>,
+        matrix: BandMatrix<F>,
+        mut rhs: Vec<T>,
+    ) -> Result<BSpline<T, F>, BSplineError> {
+        if !rhs.is_empty() {
+            matrix.solve(&mut rhs)?;
+        }
+        Ok(self.curve(knots, rhs))
+    }
+    /// Build the curve with `knots` from the solved `free` control points
+    fn curve(&self, knots: Vec<F>, free: Vec<T>) -> BSpline<T, F> {
+        let control_points = if self.fix_endpoints {
+            let mut points = Vec::with_capacity(self.n_control_points);
+            points.push(self.points[0]);
+            points.extend_from_slice(&free);
+            points.push(self.points[self.points.len() - 1]);
+            points
+        } else {
+            free
+        };
+        BSpline {
+            degree: self.degree,
+            control_points,
+            knots,
+        }
+    }
+}
+
+impl<T: InnerProduct<F>, F: Float> LeastSquares<T, F> {
+    /// Build the roughness penalty matrix `R` for the free control points of the curve with
+    /// `knots`, where `R_ij` is the integral of the product of the second derivatives of the
+    /// basis functions `i` and `j`. Also returns the penalty on the fixed control points,
+    /// `-R_ij P_j` summed over the fixed points `j`, which moves to the right hand side.
+    ///
+    /// The products of the second derivatives are polynomials of degree `2 * degree - 4` on
+    /// each knot span, so they're integrated exactly with `degree - 1` point Gauss-Legendre
+    /// quadrature.
+    fn penalty(&self, knots: &[F]) -> (BandMatrix<F>, Vec<T>) {
+        let p = self.degree;
+        let first = self.first_free();
+        let free = self.free_count();
+        let fixed = |col: usize| {
+            if col < first {
+                Some(self.points[0])
+            } else if col >= first + free {
+                Some(self.points[self.points.len() - 1])
+            } else {
+                None
+            }
+        };
+        let mut matrix = BandMatrix::new(free, p, p);
+        let mut rhs = vec![self.points[0].scale(F::zero()); free];
+        let rule = gauss_legendre::<F>(p - 1);
+        let half = F::one() / (F::one() + F::one());
+        for span in p + 1..knots.len() - p {
+            let (a, b) = (knots[span - 1], knots[span]);
+            if a == b {
+                continue;
+            }
+            let start = span - p - 1;
+            for &(x, w) in rule.iter() {
+                let t = (a + b) * half + (b - a) * half * x;
+                let w = (b - a) * half * w;
+                let d2 = &basis_derivatives(knots, p, span, t, 2)[2];
+                for (r, &br) in d2.iter().enumerate() {
+                    let row = start + r;
+                    if fixed(row).is_some() {
+                        continue;
+                    }
+                    for (s, &bs) in d2.iter().enumerate() {
+                        let col = start + s;
+                        match fixed(col) {
+                            Some(pt) => {
+                                rhs[row - first] =
+                                    rhs[row - first].difference(&pt.scale(w * br * bs))
+                            }
+                            None => matrix.add(row - first, col - first, w * br * bs),
+                        }
+                    }
+                }
+            }
+        }
+        (matrix, rhs)
+    }
+    /// Choose the smoothing parameter by minimizing the generalized cross-validation score
+    /// over a log spaced grid of values, then refining the best value by golden section
+    /// search. The grid is relative to the ratio of the traces of the data and penalty
+    /// matrices, which puts the two terms on the same scale.
+    fn choose_smoothing(
+        &self,
+        knots: &[F],
+        data: &BandMatrix<F>,
+        rhs: &[T],
+        penalty: &BandMatrix<F>,
+        penalty_rhs: &[T],
+    ) -> Result<F, BSplineError> {
+        let free = self.free_count();
+        let trace = |m: &BandMatrix<F>| (0..free).fold(F::zero(), |t, i| t + m.get(i, i));
+        let scale = trace(data) / trace(penalty);
+        let score = |log_lambda: F| {
+            self.gcv_score(
+                knots,
+                data,
+                rhs,
+                penalty,
+                penalty_rhs,
+                scale * log_lambda.exp(),
+            )
+        };
+        let ln10 = from_usize::<F>(10).ln();
+        let step = ln10 / from_usize(2);
+        let grid: Vec<F> = (0..=32)
+            .map(|i| from_usize::<F>(i) * step - from_usize::<F>(8) * ln10)
+            .collect();
+        let scores: Vec<Option<F>> = grid.iter().map(|&x| score(x)).collect();
+        let best = (0..grid.len())
+            .filter(|&i| scores[i].is_some())
+            .min_by(|&a, &b| scores[a].partial_cmp(&scores[b]).unwrap())
+            .ok_or(BSplineError::SingularSystem)?;
+
+        // Golden section search between the neighbours of the best grid point
+        let mut a = grid[best.saturating_sub(1)];
+        let mut b = grid[(best + 1).min(grid.len() - 1)];
+        let ratio = (from_usize::<F>(5).sqrt() - F::one()) / from_usize(2);
+        let mut x1 = b - (b - a) * ratio;
+        let mut x2 = a + (b - a) * ratio;
+        let (mut s1, mut s2) = (score(x1), score(x2));
+        for _ in 0..40 {
+            if better(s1, s2) {
+                b = x2;
+                x2 = x1;
+                s2 = s1;
+                x1 = b - (b - a) * ratio;
+                s1 = score(x1);
+            } else {
+                a = x1;
+                x1 = x2;
+                s1 = s2;
+                x2 = a + (b - a) * ratio;
+                s2 = score(x2);
+            }
+        }
+        let (x, s) = if better(s1, s2) { (x1, s1) } else { (x2, s2) };
+        let x = if better(s, scores[best]) {
+            x
+        } else {
+            grid[best]
+        };
+        Ok(scale * x.exp())
+    }
+    /// Compute the generalized cross-validation score of the fit with smoothing parameter
+    /// `lambda`, `n RSS / (n - 1.4 tr(H))^2`, where RSS is the weighted residual sum of squares
+    /// of the `n` samples and `H` is the matrix mapping the samples to the fitted values. The
+    /// trace, the effective number of parameters of the fit, is inflated by 1.4 as suggested
+    /// by Kim and Gu, which stops the score from favouring curves that nearly interpolate the
+    /// data when there are many control points. Returns `None` if the fit is singular or the
+    /// denominator isn't positive.
+    fn gcv_score(
+        &self,
+        knots: &[F],
+        data: &BandMatrix<F>,
+        rhs: &[T],
+        penalty: &BandMatrix<F>,
+        penalty_rhs: &[T],
+        lambda: F,
+    ) -> Option<F> {
+        let free = self.free_count();
+        let mut matrix = data.clone();
+        matrix.add_scaled(penalty, lambda);
+        let lu = matrix.factor().ok()?;
+        let mut points = penalized_rhs(rhs, penalty_rhs, lambda);
+        lu.solve(&mut points);
+        let curve = self.curve(knots.to_vec(), points);
+        let rss = self
+            .params
+            .iter()
+            .zip(self.points.iter())
+            .zip(self.weights.iter())
+            .fold(F::zero(), |rss, ((&u, q), &w)| {
+                let d = curve.point(u).difference(q);
+                rss + w * d.dot(&d)
+            });
+        // The trace of H is the trace of (A + λR)^-1 A, plus one for each fixed end
+        let mut trace = from_usize::<F>(2 * self.first_free());
+        for j in 0..free {
+            let mut col: Vec<F> = (0..free).map(|i| data.get(i, j)).collect();
+            lu.solve(&mut col);
+            trace = trace + col[j];
+        }
+        let n = from_usize::<F>(self.params.len());
+        let dof = n - trace * from_usize(7) / from_usize(5);
+        if dof > F::zero() {
+            Some(n * rss / (dof * dof))
+        } else {
+            None
+        }
+    }
+}
+
+/// Check if the score `a` is better (lower) than `b`, where `None` is the worst score
+fn better<F: Float>(a: Option<F>, b: Option<F>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a < b,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// Add the penalty on the fixed control points, scaled by `lambda`, to the right hand side
+fn penalized_rhs<T: VectorSpace<F>, F: Float>(rhs: &[T], penalty_rhs: &[T], lambda: F) -> Vec<T> {
+    rhs.iter()
+        .zip(penalty_rhs.iter())
+        .map(|(r, p)| r.sum(&p.scale(lambda)))
+        .collect()
+}
+
+/// Place the knots of a clamped curve of `degree` with `n_control_points` control points for
+/// fitting data with the sorted parameters `params`, following equation 9.69 of The NURBS
+/// Book. There must be at least as many parameters as control points, which ensures every
+/// knot span contains at least one parameter.
+fn fit_knots<F: Float>(params: &[F], degree: usize, n_control_points: usize) -> Vec<F> {
+    let m = params.len() - 1;
+    let n = n_control_points - 1;
+    let d = from_usize::<F>(m + 1) / from_usize(n - degree + 1);
+    let mut knots = vec![params[0]; degree + 1];
+    for j in 1..=n - degree {
+        let jd = from_usize::<F>(j) * d;
+        let i = to_usize(jd).clamp(1, m);
+        let alpha = jd - from_usize(i);
+        knots.push(params[i - 1] * (F::one() - alpha) + params[i] * alpha);
+    }
+    knots.extend(std::iter::repeat_n(params[m], degree + 1));
+    knots
+}
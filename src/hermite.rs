@@ -0,0 +1,214 @@
+use crate::basis::basis_derivatives;
+use crate::linalg::BandMatrix;
+use crate::{from_usize, knot_span, BSpline, BSplineError, Float, VectorSpace};
+
+/// The condition used to pin down the shape of an interpolating cubic curve at an end that
+/// doesn't have a prescribed derivative, see `BSpline::interpolate_hermite`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndCondition {
+    /// The second derivative of the curve is zero at the end, giving the natural cubic
+    /// spline that bends as little as possible.
+    Natural,
+    /// The first two polynomial pieces of the curve at the end are the same cubic, so the
+    /// second parameter isn't a knot. This reproduces cubic polynomials exactly, and the
+    /// second key can't have a derivative.
+    NotAKnot,
+    /// The curve is closed, with its first and second derivatives matching at the two ends.
+    /// The first and last data points must be the same.
+    Periodic,
+}
+
+impl<T: VectorSpace<F>, F: Float> BSpline<T, F> {
+    /// Find the clamped cubic B-spline curve passing through the `keys`, where each key is a
+    /// parameter value and the point the curve passes through at that parameter. The curve
+    /// can also be given the first derivative it must have at each key in `derivatives`,
+    /// which must either be empty or have one optional derivative per key.
+    ///
+    /// The keys are used as the knots of the curve, where keys with a derivative are double
+    /// knots so the curve has the freedom to match it. The curve is twice continuously
+    /// differentiable at keys without a derivative and continuously differentiable at keys
+    /// with one. The derivative at an end clamps the tangent of the curve there, otherwise
+    /// the shape at the end is set by `end_condition`. For `EndCondition::Periodic` the
+    /// first and last keys must have the same point, and a derivative given at either end is
+    /// used as the tangent at both ends, if both ends have one they must be the same. The
+    /// curve then gets an extra knot in the middle of its first span so it can also keep its
+    /// second derivative continuous across the ends.
+    ///
+    /// The key parameters must be finite and strictly increasing and there must be at least
+    /// two keys, or four with `EndCondition::NotAKnot` at an end without a derivative,
+    /// otherwise an error is returned. An error is also returned if `EndCondition::NotAKnot`
+    /// applies at an end and the second key from that end has a derivative, as the knot it
+    /// would remove is needed to match the derivative.
+    pub fn interpolate_hermite(
+        keys: &[(F, T)],
+        derivatives: &[Option<T>],
+        end_condition: EndCondition,
+    ) -> Result<BSpline<T, F>, BSplineError>
+    where
+        T: PartialEq,
+    {
+        let p = 3;
+        if keys.len() < 2 {
+            return Err(BSplineError::TooFewControlPoints {
+                degree: p,
+                found: keys.len(),
+            });
+        }
+        let none = vec![None; keys.len()];
+        let derivatives = if derivatives.is_empty() {
+            &none[..]
+        } else {
+            derivatives
+        };
+        if derivatives.len() != keys.len() {
+            return Err(BSplineError::InvalidDerivativeCount {
+                expected: keys.len(),
+                found: derivatives.len(),
+            });
+        }
+        if let Some(index) = keys.iter().position(|(u, _)| !u.is_finite()) {
+            return Err(BSplineError::NonFiniteKnot { index });
+        }
+        if let Some(index) = (1..keys.len()).find(|&i| keys[i].0 < keys[i - 1].0) {
+            return Err(BSplineError::UnsortedKnots { index });
+        }
+        if (1..keys.len()).any(|i| keys[i].0 == keys[i - 1].0) {
+            return Err(BSplineError::SingularSystem);
+        }
+
+        let n = keys.len() - 1;
+        let (u0, un) = (keys[0].0, keys[n].0);
+        let periodic = end_condition == EndCondition::Periodic;
+        let seam = if periodic {
+            if keys[0].1 != keys[n].1 {
+                return Err(BSplineError::PeriodicMismatch);
+            }
+            match (derivatives[0], derivatives[n]) {
+                (Some(a), Some(b)) if a != b => return Err(BSplineError::PeriodicMismatch),
+                (a, b) => a.or(b),
+            }
+        } else {
+            None
+        };
+        let mut interior = Vec::new();
+        if seam.is_some() {
+            interior.push((u0 + keys[1].0) / from_usize(2));
+        }
+        for k in 1..n {
+            interior.push(keys[k].0);
+            if derivatives[k].is_some() {
+                interior.push(keys[k].0);
+            }
+        }
+        if end_condition == EndCondition::NotAKnot {
+            let removals = [derivatives[0].is_none(), derivatives[n].is_none()];
+            let needed = removals.iter().filter(|&&r| r).count();
+            if n < 3 && needed > 0 {
+                return Err(BSplineError::TooFewControlPoints {
+                    degree: p,
+                    found: keys.len(),
+                });
+            }
+            if removals[0] && derivatives[1].is_some() {
+                return Err(BSplineError::NotAKnotDerivative { index: 1 });
+            }
+            if removals[1] && derivatives[n - 1].is_some() {
+                return Err(BSplineError::NotAKnotDerivative { index: n - 1 });
+            }
+            if removals[0] {
+                interior.remove(0);
+            }
+            if removals[1] {
+                interior.pop();
+            }
+        }
+        let mut knots = vec![u0; p + 1];
+        knots.extend_from_slice(&interior);
+        knots.extend(std::iter::repeat_n(un, p + 1));
+
+        let zero = keys[0].1.scale(F::zero());
+        let mut rows: Vec<Constraint<T, F>> = Vec::new();
+        let one = F::one();
+        match (end_condition, derivatives[0]) {
+            (EndCondition::Natural, None) => rows.push(Constraint::new(vec![(u0, 2, one)], zero)),
+            (EndCondition::Periodic, _) => match seam {
+                Some(d) => rows.push(Constraint::new(vec![(u0, 1, one)], d)),
+                None => rows.push(Constraint::new(vec![(u0, 1, one), (un, 1, -one)], zero)),
+            },
+            _ => {}
+        }
+        for (k, (&(u, point), derivative)) in keys.iter().zip(derivatives.iter()).enumerate() {
+            rows.push(Constraint::new(vec![(u, 0, one)], point));
+            if let Some(d) = derivative {
+                if !periodic || (k != 0 && k != n) {
+                    rows.push(Constraint::new(vec![(u, 1, one)], *d));
+                }
+            }
+        }
+        match (end_condition, derivatives[n]) {
+            (EndCondition::Natural, None) => rows.push(Constraint::new(vec![(un, 2, one)], zero)),
+            (EndCondition::Periodic, _) => {
+                if let Some(d) = seam {
+                    rows.push(Constraint::new(vec![(un, 1, one)], d));
+                }
+                rows.push(Constraint::new(vec![(u0, 2, one), (un, 2, -one)], zero));
+            }
+            _ => {}
+        }
+
+        let size = knots.len() - p - 1;
+        debug_assert_eq!(rows.len(), size);
+        let entries: Vec<Vec<(usize, F)>> = rows
+            .iter()
+            .map(|row| {
+                let mut entries = Vec::new();
+                for &(u, order, sign) in row.terms.iter() {
+                    let span = knot_span(&knots, p, u);
+                    let ders = basis_derivatives(&knots, p, span, u, order);
+                    for (r, &b) in ders[order].iter().enumerate() {
+                        entries.push((span - p - 1 + r, sign * b));
+                    }
+                }
+                entries
+            })
+            .collect();
+        let lower = entries
+            .iter()
+            .enumerate()
+            .flat_map(|(i, e)| e.iter().map(move |&(j, _)| i.saturating_sub(j)))
+            .max()
+            .unwrap_or(0);
+        let upper = entries
+            .iter()
+            .enumerate()
+            .flat_map(|(i, e)| e.iter().map(move |&(j, _)| j.saturating_sub(i)))
+            .max()
+            .unwrap_or(0);
+        let mut matrix = BandMatrix::new(size, lower, upper);
+        for (i, e) in entries.iter().enumerate() {
+            for &(j, b) in e.iter() {
+                matrix.add(i, j, b);
+            }
+        }
+        let mut control_points: Vec<T> = rows.into_iter().map(|row| row.value).collect();
+        matrix.solve(&mut control_points)?;
+        Ok(BSpline {
+            degree: p,
+            control_points,
+            knots,
+        })
+    }
+}
+
+/// A row of the interpolation system, constraining the sum of the curve derivatives in
+/// `terms`, each given by its parameter, order and sign, to equal `value`
+struct Constraint<T, F> {
+    terms: Vec<(F, usize, F)>,
+    value: T,
+}
+
+impl<T, F> Constraint<T, F> {
+    fn new(terms: Vec<(F, usize, F)>, value: T) -> Constraint<T, F> {
+        Constraint { terms, value }
+    }
+}
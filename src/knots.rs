@@ -0,0 +1,167 @@
+use std::slice::Iter;
+
+use crate::{from_usize, BSpline, BSplineError, Float, Interpolate};
+
+/// A knot vector for a B-spline curve of a specific degree, built from one of the standard
+/// knot layouts instead of listing the knots by hand. The knots can be adjusted further by
+/// repeating interior breakpoints with `with_multiplicity` and moving the domain of the curve
+/// with `rescale`, then passed to `BSpline::with_knot_vector` to build the curve.
+///
+/// For example the knots of the cardinal cubic B-spline in the crate documentation can be
+/// built with:
+///
+/// ```rust
+/// use bspline::KnotVector;
+///
+/// let knots = KnotVector::clamped(3, 7).rescale(-2.0, 2.0);
+/// assert_eq!(
+///     knots.knots().cloned().collect::<Vec<f64>>(),
+///     vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnotVector<F: Float> {
+    /// Degree of the curve the knots are for
+    pub(crate) degree: usize,
+    /// The knot values, sorted in non-decreasing order
+    pub(crate) knots: Vec<F>,
+}
+
+impl<F: Float> KnotVector<F> {
+    /// Create a uniform knot vector for a curve of `degree` with `control_points` control
+    /// points, where the knots are evenly spaced integers starting at 0. The curve won't
+    /// start or end at its first and last control points, and its knot domain is
+    /// `[degree, control_points]`.
+    ///
+    /// There must be more control points than the degree, otherwise the function will panic.
+    pub fn uniform(degree: usize, control_points: usize) -> KnotVector<F> {
+        check_control_points(degree, control_points);
+        KnotVector {
+            degree,
+            knots: (0..control_points + degree + 1).map(from_usize).collect(),
+        }
+    }
+    /// Create a clamped, also known as open uniform, knot vector for a curve of `degree` with
+    /// `control_points` control points. The first and last knots are repeated `degree + 1`
+    /// times so the curve starts and ends at its first and last control points, and the
+    /// interior knots are evenly spaced integers. The knot domain of the curve is
+    /// `[0, control_points - degree]`.
+    ///
+    /// There must be more control points than the degree, otherwise the function will panic.
+    pub fn clamped(degree: usize, control_points: usize) -> KnotVector<F> {
+        check_control_points(degree, control_points);
+        let end = control_points - degree;
+        let knots = (0..control_points + degree + 1)
+            .map(|i| from_usize(i.saturating_sub(degree).min(end)))
+            .collect();
+        KnotVector { degree, knots }
+    }
+    /// Create a periodic knot vector for a closed curve of `degree` through `control_points`
+    /// control points. The knots are evenly spaced integers and the curve must be given its
+    /// control points followed by the first `degree` of them again, wrapping around so the
+    /// curve joins up smoothly with itself. The knot vector is for `control_points + degree`
+    /// control points and the knot domain of the curve is `[0, control_points]`.
+    /// `PeriodicBSpline` builds closed curves with these knots and handles the wrapping.
+    ///
+    /// There must be more control points than the degree, otherwise the function will panic.
+    pub fn periodic(degree: usize, control_points: usize) -> KnotVector<F> {
+        check_control_points(degree, control_points);
+        let offset = from_usize::<F>(degree);
+        let knots = (0..control_points + 2 * degree + 1)
+            .map(|i| from_usize::<F>(i) - offset)
+            .collect();
+        KnotVector { degree, knots }
+    }
+    /// Repeat the interior breakpoint `u` so it appears `multiplicity` times in the knot
+    /// vector, adding it if it's not already a knot. Each repetition lowers the continuity of
+    /// the curve at `u` by one and needs one more control point, a knot repeated `degree`
+    /// times makes the curve pass through a control point and `degree + 1` times allows a
+    /// break in the curve.
+    ///
+    /// `u` must be strictly inside the `knot_domain` and the multiplicity must be between 1
+    /// and `degree + 1`, otherwise the function will panic.
+    pub fn with_multiplicity(mut self, u: F, multiplicity: usize) -> KnotVector<F> {
+        let (min, max) = self.knot_domain();
+        if !(u > min && u < max) {
+            panic!("Only interior breakpoints can be repeated");
+        }
+        if multiplicity == 0 {
+            panic!("Knot multiplicity must be at least 1");
+        }
+        if multiplicity > self.degree + 1 {
+            panic!(
+                "Knot multiplicity {} is too high, at most {} repetitions are allowed",
+                multiplicity,
+                self.degree + 1
+            );
+        }
+        let start = self.knots.iter().position(|&k| k >= u).unwrap();
+        let end = start + self.knots[start..].iter().take_while(|&&k| k == u).count();
+        self.knots
+            .splice(start..end, std::iter::repeat_n(u, multiplicity));
+        self
+    }
+    /// Linearly map the knots so the knot domain becomes `[min, max]`, keeping the relative
+    /// spacing of the knots. `min` and `max` must be finite and `min` must be less than `max`,
+    /// otherwise the function will panic.
+    pub fn rescale(mut self, min: F, max: F) -> KnotVector<F> {
+        if !min.is_finite() || !max.is_finite() || min >= max {
+            panic!("The rescaled knot domain must be a finite, non-empty interval");
+        }
+        let (a, b) = self.knot_domain();
+        let scale = (max - min) / (b - a);
+        // The end of the domain is set directly so it's exact despite rounding
+        for k in self.knots.iter_mut() {
+            *k = if *k == b { max } else { min + (*k - a) * scale };
+        }
+        self
+    }
+    /// Get the degree of the curve the knots are for.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+    /// Get the number of control points a curve using these knots needs.
+    pub fn control_point_count(&self) -> usize {
+        self.knots.len() - self.degree - 1
+    }
+    /// Get an iterator over the knots.
+    pub fn knots(&self) -> Iter<'_, F> {
+        self.knots.iter()
+    }
+    /// Get the min and max knot domain values of a curve using these knots.
+    pub fn knot_domain(&self) -> (F, F) {
+        (
+            self.knots[self.degree],
+            self.knots[self.knots.len() - 1 - self.degree],
+        )
+    }
+}
+
+impl<F: Float> From<KnotVector<F>> for Vec<F> {
+    fn from(knots: KnotVector<F>) -> Vec<F> {
+        knots.knots
+    }
+}
+
+impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
+    /// Create a new B-spline curve that will interpolate the `control_points` using the
+    /// degree and knots of `knots`. As with `new` the number of control points must match
+    /// the `control_point_count` of the knots, otherwise the function will panic.
+    pub fn with_knot_vector(control_points: Vec<T>, knots: KnotVector<F>) -> BSpline<T, F> {
+        BSpline::new(knots.degree, control_points, knots.knots)
+    }
+    /// Create a new B-spline curve using the degree and knots of `knots`, returning an error
+    /// instead of panicking if the parameters don't describe a valid curve as with `try_new`.
+    pub fn try_with_knot_vector(
+        control_points: Vec<T>,
+        knots: KnotVector<F>,
+    ) -> Result<BSpline<T, F>, BSplineError> {
+        BSpline::try_new(knots.degree, control_points, knots.knots)
+    }
+}
+
+fn check_control_points(degree: usize, control_points: usize) {
+    if control_points <= degree {
+        panic!("Too few control points for curve");
+    }
+}
@@ -0,0 +1,186 @@
+use crate::quadrature::gauss_legendre;
+use crate::{from_usize, BSpline, Float, InnerProduct};
+
+/// Number of points in the Gauss-Legendre rule used on each interval when measuring length
+const RULE_POINTS: usize = 8;
+/// Maximum number of times an interval is split in half when measuring length
+const MAX_DEPTH: usize = 16;
+/// Maximum number of iterations of the search for the parameter at an arc length
+const MAX_ITERATIONS: usize = 64;
+
+impl<T: InnerProduct<F>, F: Float> BSpline<T, F> {
+    /// Compute the length of the curve between the parameters `t0` and `t1`, which **must**
+    /// be in the inclusive range of values returned by `knot_domain`. If `t1` is less than
+    /// `t0` the length is negative.
+    ///
+    /// The length is the integral of the speed of the curve, the norm of its derivative, which
+    /// is integrated over each knot span with adaptive Gauss-Legendre quadrature. Intervals
+    /// are split in half until the estimates of their length agree to close to the precision
+    /// of `F`.
+    pub fn arc_length(&self, t0: F, t1: F) -> F {
+        if t1 < t0 {
+            return -self.arc_length(t1, t0);
+        }
+        ArcLength::new(self).length(t0, t1)
+    }
+    /// Find the parameter `t` where the length of the curve from the start of its
+    /// `knot_domain` to `t` is `s`, the inverse of `arc_length`. Lengths less than zero give
+    /// the start of the domain and lengths greater than the length of the whole curve give the
+    /// end. Where the curve stops moving with parts of zero length the first matching
+    /// parameter isn't guaranteed.
+    pub fn param_at_length(&self, s: F) -> F {
+        let arc = ArcLength::new(self);
+        arc.param_at_length(s, &arc.span_lengths())
+    }
+    /// Sample `n` points on the curve evenly spaced by arc length, including both ends of the
+    /// curve. Unlike `sample`, which steps evenly through the parameter `t`, moving through
+    /// these points at a fixed rate moves along the curve at a constant speed.
+    pub fn sample_arc_length(&self, n: usize) -> Vec<T> {
+        let (min, max) = self.knot_domain();
+        let arc = ArcLength::new(self);
+        let lengths = arc.span_lengths();
+        let total = lengths.iter().fold(F::zero(), |l, &x| l + x);
+        (0..n)
+            .map(|i| match i {
+                0 => self.point(min),
+                _ if i == n - 1 => self.point(max),
+                _ => {
+                    let s = total * from_usize(i) / from_usize(n - 1);
+                    self.point(arc.param_at_length(s, &lengths))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Measures the length of a curve, holding the quadrature rule and the breakpoints of the
+/// curve's knot spans.
+struct ArcLength<'a, T: InnerProduct<F>, F: Float> {
+    curve: &'a BSpline<T, F>,
+    /// The Gauss-Legendre rule on `[-1, 1]`
+    rule: Vec<(F, F)>,
+    /// The relative error the length estimates are refined to
+    tolerance: F,
+    /// The breakpoints bounding each non-empty knot span of the domain
+    breakpoints: Vec<F>,
+}
+
+impl<'a, T: InnerProduct<F>, F: Float> ArcLength<'a, T, F> {
+    fn new(curve: &'a BSpline<T, F>) -> ArcLength<'a, T, F> {
+        let (min, max) = curve.knot_domain();
+        let mut breakpoints = vec![min];
+        for &k in curve.knots.iter().filter(|&&k| k > min && k < max) {
+            if k > breakpoints[breakpoints.len() - 1] {
+                breakpoints.push(k);
+            }
+        }
+        breakpoints.push(max);
+        ArcLength {
+            curve,
+            rule: gauss_legendre(RULE_POINTS),
+            tolerance: precision::<F>() * from_usize(1024),
+            breakpoints,
+        }
+    }
+    /// Compute the length of the curve between `t0` and `t1`, splitting the interval at the
+    /// breakpoints where the curve's derivative may not be smooth
+    fn length(&self, t0: F, t1: F) -> F {
+        let mut length = F::zero();
+        let mut a = t0;
+        for &k in self.breakpoints.iter().filter(|&&k| k > t0 && k < t1) {
+            length = length + self.span_length(a, k);
+            a = k;
+        }
+        length + self.span_length(a, t1)
+    }
+    /// Compute the length of the curve over each knot span
+    fn span_lengths(&self) -> Vec<F> {
+        self.breakpoints
+            .windows(2)
+            .map(|w| self.span_length(w[0], w[1]))
+            .collect()
+    }
+    /// Find the parameter where the length of the curve from the start of the domain is `s`,
+    /// given the `span_lengths`
+    fn param_at_length(&self, s: F, lengths: &[F]) -> F {
+        let (min, max) = self.curve.knot_domain();
+        if s <= F::zero() {
+            return min;
+        }
+        // Find the knot span containing the length and the length remaining at its start
+        let mut remaining = s;
+        let mut span = None;
+        for (w, &length) in self.breakpoints.windows(2).zip(lengths.iter()) {
+            if remaining < length {
+                span = Some((w[0], w[1], length));
+                break;
+            }
+            remaining = remaining - length;
+        }
+        let (a, b, length) = match span {
+            Some(span) => span,
+            None => return max,
+        };
+        // Newton's method on the length from the start of the span, falling back to
+        // bisection whenever a step leaves the interval known to contain the parameter
+        let (mut lo, mut hi) = (a, b);
+        let mut t = a + (b - a) * remaining / length;
+        for _ in 0..MAX_ITERATIONS {
+            let error = self.span_length(a, t) - remaining;
+            if error.abs() <= self.tolerance * length {
+                break;
+            }
+            if error > F::zero() {
+                hi = t;
+            } else {
+                lo = t;
+            }
+            let speed = self.curve.derivative(t, 1).norm();
+            let next = t - error / speed;
+            t = if next > lo && next < hi {
+                next
+            } else {
+                (lo + hi) / from_usize(2)
+            };
+        }
+        t
+    }
+    /// Compute the length of the curve over `[a, b]`, which must be within one knot span
+    fn span_length(&self, a: F, b: F) -> F {
+        if b <= a {
+            return F::zero();
+        }
+        self.adaptive(a, b, self.gauss(a, b), MAX_DEPTH)
+    }
+    /// Refine the estimate `whole` of the length of `[a, b]` by splitting it in half until
+    /// the sum of the halves agrees with the estimate for the whole interval
+    fn adaptive(&self, a: F, b: F, whole: F, depth: usize) -> F {
+        let mid = (a + b) / from_usize(2);
+        let left = self.gauss(a, mid);
+        let right = self.gauss(mid, b);
+        let sum = left + right;
+        if depth == 0 || (sum - whole).abs() <= self.tolerance * sum {
+            sum
+        } else {
+            self.adaptive(a, mid, left, depth - 1) + self.adaptive(mid, b, right, depth - 1)
+        }
+    }
+    /// Estimate the length of `[a, b]` with the Gauss-Legendre rule
+    fn gauss(&self, a: F, b: F) -> F {
+        let half = (b - a) / from_usize(2);
+        let center = (a + b) / from_usize(2);
+        self.rule.iter().fold(F::zero(), |l, &(x, w)| {
+            l + w * half * self.curve.derivative(center + half * x, 1).norm()
+        })
+    }
+}
+
+/// Find the precision of `F`, the smallest power of two that changes one when added to it
+fn precision<F: Float>() -> F {
+    let two = F::one() + F::one();
+    let mut e = F::one();
+    while F::one() + e / two > F::one() {
+        e = e / two;
+    }
+    e
+}
@@ -25,6 +25,17 @@
 //! let spline = bspline::BSpline::new(degree, points, knots);
 //! ```
 //!
+//! The knots don't need to be written out by hand, the same clamped knot vector can be
+//! generated with `KnotVector`:
+//!
+//! ```rust
+//! use bspline::{BSpline, KnotVector};
+//!
+//! let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+//! let knots = KnotVector::clamped(3, points.len()).rescale(-2.0, 2.0);
+//! let spline = BSpline::with_knot_vector(points, knots);
+//! ```
+//!
 //! # Readings on B-splines
 //!
 //! The library assumes you are familiar at some level with how B-splines work, e.g. how
@@ -40,8 +51,40 @@
 //! - [A nice set of interactive examples](https://www.ibiblio.org/e-notes/Splines/Intro.htm)
 //!
 
+// nalgebra's RealField has the compound assignment operators but num_traits::Float doesn't,
+// so float updates are written out in full to work with both
+#![cfg_attr(feature = "nalgebra-support", allow(clippy::assign_op_pattern))]
+
 use std::ops::{Add, Mul};
 use std::slice::Iter;
+
+mod basis;
+mod bezier;
+mod degree;
+mod error;
+mod fit;
+mod hermite;
+mod knots;
+mod length;
+mod linalg;
+mod nurbs;
+mod periodic;
+mod project;
+mod quadrature;
+mod refine;
+mod remove;
+mod surface;
+mod volume;
+
+pub use bezier::BezierSegment;
+pub use error::BSplineError;
+pub use fit::{FitOptions, Parameterization, Smoothing};
+pub use hermite::EndCondition;
+pub use knots::KnotVector;
+pub use nurbs::NurbsCurve;
+pub use periodic::PeriodicBSpline;
+pub use surface::{BSplineSurface, NurbsSurface};
+pub use volume::{BSplineVolume, FreeFormDeformation};
 extern crate trait_set;
 use trait_set::trait_set;
 
@@ -84,6 +127,69 @@ impl<T: Mul<F, Output = T> + Add<Output = T> + Copy, F: Float> Interpolate<F> fo
     }
 }
 
+/// The vector space trait provides the operations on top of `Interpolate` needed to compute
+/// derivatives of the curve. The derivative control points are differences of the curve's
+/// control points scaled by the knot spacing, which can't be expressed as an interpolation.
+///
+/// As with `Interpolate` a default implementation is provided for all `T` that are
+/// `Mul<F, Output = T> + Add<Output = T> + Copy`, so most types used with `BSpline` will
+/// get this trait for free.
+pub trait VectorSpace<F>: Interpolate<F> + Copy {
+    /// Compute the sum `self + other`
+    fn sum(&self, other: &Self) -> Self;
+    /// Compute the difference `self - other`
+    fn difference(&self, other: &Self) -> Self;
+    /// Scale `self` by the scalar `s`
+    fn scale(&self, s: F) -> Self;
+}
+
+impl<T: Mul<F, Output = T> + Add<Output = T> + Copy, F: Float> VectorSpace<F> for T {
+    fn sum(&self, other: &Self) -> Self {
+        *self + *other
+    }
+    fn difference(&self, other: &Self) -> Self {
+        *self + *other * (-F::one())
+    }
+    fn scale(&self, s: F) -> Self {
+        *self * s
+    }
+}
+
+/// The inner product trait adds a dot product to the `VectorSpace` operations, which gives
+/// the control points a notion of length and distance. It's needed by the operations that
+/// measure the curve, such as reporting how far an approximation deviates from the original.
+///
+/// Implementations are provided for `f32` and `f64`, other point types should implement
+/// `dot` as the sum of the products of their components.
+pub trait InnerProduct<F: Float>: VectorSpace<F> {
+    /// Compute the dot product of `self` and `other`
+    fn dot(&self, other: &Self) -> F;
+    /// Compute the length of `self`
+    fn norm(&self) -> F {
+        self.dot(self).sqrt()
+    }
+}
+
+impl InnerProduct<f32> for f32 {
+    fn dot(&self, other: &f32) -> f32 {
+        self * other
+    }
+}
+
+impl InnerProduct<f64> for f64 {
+    fn dot(&self, other: &f64) -> f64 {
+        self * other
+    }
+}
+
+/// The cross product trait gives three dimensional control points the product needed to find
+/// the normals of surfaces. Types implementing this should compute the usual right handed
+/// cross product of their components.
+pub trait Cross<F: Float>: InnerProduct<F> {
+    /// Compute the cross product of `self` and `other`
+    fn cross(&self, other: &Self) -> Self;
+}
+
 /// Represents a B-spline curve that will use polynomials of the specified degree
 /// to interpolate between the control points given the knots.
 #[derive(Clone, Debug)]
@@ -106,6 +212,7 @@ impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
     /// Your curve must have a valid number of control points and knots or the function will panic. A B-spline
     /// curve requires at least as one more control point than the degree (`control_points.len() >
     /// degree`) and the number of knots should be equal to `control_points.len() + degree + 1`.
+    /// Use `try_new` to validate curves from untrusted input without panicking.
     pub fn new(degree: usize, control_points: Vec<T>, mut knots: Vec<F>) -> BSpline<T, F> {
         if control_points.len() <= degree {
             panic!("Too few control points for curve");
@@ -124,22 +231,137 @@ impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
             knots,
         }
     }
+    /// Create a new B-spline curve of the desired `degree` that will interpolate the
+    /// `control_points` using the `knots`, returning an error instead of panicking if the
+    /// parameters don't describe a valid curve. Unlike `new` the knots are not sorted for you,
+    /// the curve is rejected if:
+    ///
+    /// - there are `degree` or fewer control points,
+    /// - the number of knots isn't `control_points.len() + degree + 1`,
+    /// - any of the knots are NaN or infinite,
+    /// - the knots are not sorted in non-decreasing order,
+    /// - any knot value is repeated more than `degree + 1` times,
+    /// - the knot domain of the curve is empty.
+    pub fn try_new(
+        degree: usize,
+        control_points: Vec<T>,
+        knots: Vec<F>,
+    ) -> Result<BSpline<T, F>, BSplineError> {
+        check_knots(degree, control_points.len(), &knots)?;
+        Ok(BSpline {
+            degree,
+            control_points,
+            knots,
+        })
+    }
     /// Compute a point on the curve at `t`, the parameter **must** be in the inclusive range
     /// of values returned by `knot_domain`. If `t` is out of bounds this function will assert
-    /// on debug builds and on release builds you'll likely get an out of bounds crash.
+    /// on debug builds, while on release builds it's clamped to the first or last knot span
+    /// and the curve is extrapolated as with `point_extrapolated`. Use `try_point` or
+    /// `point_clamped` for values of `t` that may be out of bounds.
+    ///
+    /// Curves with a degree less than 16 are evaluated entirely on the stack, so this never
+    /// allocates. The same holds for the other evaluation methods and `derivative`.
     pub fn point(&self, t: F) -> T {
         debug_assert!(t >= self.knot_domain().0 && t <= self.knot_domain().1);
-        // Find the first index with a knot value greater than the t we're searching for. We want
-        // to find i such that: knot[i] <= t < knot[i + 1]
-        let i = match upper_bounds(&self.knots[..], t) {
-            Some(x) if x == 0 => self.degree,
-            Some(x) if x >= self.knots.len() - self.degree - 1 => {
-                self.knots.len() - self.degree - 1
+        let i = self.knot_span(t);
+        self.de_boor_iterative(t, i)
+    }
+    /// Compute a point on the curve at `t`, returning `None` if `t` is outside the inclusive
+    /// range of values returned by `knot_domain` or is NaN.
+    pub fn try_point(&self, t: F) -> Option<T> {
+        let (min, max) = self.knot_domain();
+        if t >= min && t <= max {
+            Some(self.de_boor_iterative(t, self.knot_span(t)))
+        } else {
+            None
+        }
+    }
+    /// Compute a point on the curve at `t`, clamping `t` to the range of values returned by
+    /// `knot_domain`. This is useful when sampling loops accumulate floating point error and
+    /// step slightly past the ends of the curve. Infinite values of `t` are clamped like any
+    /// other, while a NaN `t` can't be clamped and evaluates to NaN, giving a point with NaN
+    /// coordinates for the usual point types.
+    pub fn point_clamped(&self, t: F) -> T {
+        let (min, max) = self.knot_domain();
+        let t = if t < min {
+            min
+        } else if t > max {
+            max
+        } else {
+            t
+        };
+        self.de_boor_iterative(t, self.knot_span(t))
+    }
+    /// Compute a point on the curve at `t`, extrapolating the curve outside of the range of
+    /// values returned by `knot_domain`. Values of `t` before the domain are evaluated by
+    /// extending the polynomial of the first segment of the curve and values after it by
+    /// extending the polynomial of the last segment. Within the domain this is the same as `point`.
+    /// A NaN or infinite `t` evaluates to NaN or infinite values, giving a point with
+    /// non-finite coordinates for the usual point types.
+    pub fn point_extrapolated(&self, t: F) -> T {
+        self.de_boor_iterative(t, self.knot_span(t))
+    }
+    /// Sample `n` points on the curve at evenly spaced values of `t` across the curve's
+    /// `knot_domain`, including both of its ends.
+    pub fn sample(&self, n: usize) -> Vec<T> {
+        let (min, max) = self.knot_domain();
+        let ts: Vec<F> = match n {
+            0 => Vec::new(),
+            1 => vec![min],
+            _ => {
+                let step = (max - min) / from_usize(n - 1);
+                (0..n)
+                    .map(|i| {
+                        if i == n - 1 {
+                            max
+                        } else {
+                            min + step * from_usize(i)
+                        }
+                    })
+                    .collect()
             }
-            Some(x) => x,
-            None => self.knots.len() - self.degree - 1,
         };
-        self.de_boor_iterative(t, i)
+        let mut points = vec![self.control_points[0]; n];
+        self.evaluate_many(&ts, &mut points);
+        points
+    }
+    /// Get an iterator sampling the curve from the start of its `knot_domain` to the end with
+    /// a fixed `step` size between the `t` values. The iterator returns `(t, point)` pairs and
+    /// the last value of `t` is clamped to the domain in case of floating point error. If
+    /// `step` isn't a finite positive value only the start of the curve is sampled.
+    pub fn sample_iter(&self, step: F) -> SampleIter<'_, T, F> {
+        let (min, max) = self.knot_domain();
+        let steps = if step > F::zero() && step.is_finite() {
+            to_usize((max - min) / step)
+        } else {
+            0
+        };
+        SampleIter {
+            spline: self,
+            step,
+            sample: 0,
+            steps,
+            span: self.knot_span(min),
+        }
+    }
+    /// Evaluate the curve at each value in `ts` and write the points to `points`, which must
+    /// be the same length as `ts`. The values of `t` must be within the curve's `knot_domain`,
+    /// as with `point`. When `ts` is sorted in increasing order the knot spans are found by
+    /// stepping through the knots from one parameter to the next instead of searching the
+    /// whole knot vector for each one.
+    pub fn evaluate_many(&self, ts: &[F], points: &mut [T]) {
+        assert_eq!(ts.len(), points.len());
+        let mut span = self.degree + 1;
+        for (t, p) in ts.iter().zip(points.iter_mut()) {
+            debug_assert!(*t >= self.knot_domain().0 && *t <= self.knot_domain().1);
+            span = self.next_knot_span(*t, span);
+            *p = self.de_boor_iterative(*t, span);
+        }
+    }
+    /// Get the degree of the polynomials used by the curve.
+    pub fn degree(&self) -> usize {
+        self.degree
     }
     /// Get an iterator over the control points.
     pub fn control_points(&self) -> Iter<T> {
@@ -151,36 +373,334 @@ impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
     }
     /// Get the min and max knot domain values for finding the `t` range to compute
     /// the curve over. The curve is only defined over the inclusive range `[min, max]`,
-    /// passing a `t` value outside of this range will result in an assert on debug builds
-    /// and likely a crash on release builds.
+    /// passing a `t` value outside of this range to `point` will result in an assert on debug
+    /// builds and an extrapolated point on release builds. See `try_point`, `point_clamped` and
+    /// `point_extrapolated` for evaluating the curve with `t` values that may be out of range.
     pub fn knot_domain(&self) -> (F, F) {
         (
             self.knots[self.degree],
             self.knots[self.knots.len() - 1 - self.degree],
         )
     }
+    /// Check if the knot vector is clamped, with the first and last knots repeated
+    /// `degree + 1` times so the curve starts and ends on its first and last control points.
+    fn is_clamped(&self) -> bool {
+        let p = self.degree;
+        let m = self.knots.len() - 1;
+        self.knots[..=p].iter().all(|&k| k == self.knots[0])
+            && self.knots[m - p..].iter().all(|&k| k == self.knots[m])
+    }
+    /// Find the index `i` of the knot span containing `t`, such that
+    /// `knot[i - 1] <= t < knot[i]`. The index is clamped to the non-empty spans making up
+    /// the curve's domain, so `t == knot_domain().1` falls in the last span and values
+    /// outside the domain fall in the first or last span.
+    fn knot_span(&self, t: F) -> usize {
+        knot_span(&self.knots, self.degree, t)
+    }
+    /// Find the knot span containing `t` by stepping forward from `span`, which was the
+    /// knot span of a previous parameter value. Falls back to searching the knot vector
+    /// with `knot_span` if `t` is before `span`.
+    fn next_knot_span(&self, t: F, span: usize) -> usize {
+        let last = self.knots.len() - self.degree - 1;
+        if t < self.knots[span - 1] {
+            return self.knot_span(t);
+        }
+        let mut i = span;
+        while i < last && t >= self.knots[i] {
+            i += 1;
+        }
+        if self.knots[i - 1] < self.knots[i] {
+            i
+        } else {
+            self.knot_span(t)
+        }
+    }
     /// Iteratively compute de Boor's B-spline algorithm, this computes the recursive
     /// de Boor algorithm tree from the bottom up. At each level we use the results
     /// from the previous one to compute this level and store the results in the
     /// array indices we no longer need to compute the current level (the left one
     /// used computing node j).
     fn de_boor_iterative(&self, t: F, i_start: usize) -> T {
-        let mut tmp = Vec::with_capacity(self.degree + 1);
-        for j in 0..=self.degree {
-            let p = j + i_start - self.degree - 1;
-            tmp.push(self.control_points[p]);
-        }
-        for lvl in 0..self.degree {
-            let k = lvl + 1;
-            for j in 0..self.degree - lvl {
-                let i = j + k + i_start - self.degree;
-                let alpha =
-                    (t - self.knots[i - 1]) / (self.knots[i + self.degree - k] - self.knots[i - 1]);
-                debug_assert!(alpha.is_finite());
-                tmp[j] = tmp[j].interpolate(&tmp[j + 1], alpha);
-            }
+        let points = &self.control_points[i_start - self.degree - 1..i_start];
+        with_scratch(points, |tmp| {
+            de_boor(tmp, &self.knots, self.degree, t, i_start)
+        })
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> BSpline<T, F> {
+    /// Compute the `order`-th derivative of the curve at `t`, where an `order` of 0 is the
+    /// point on the curve itself. As with `point` the parameter **must** be in the inclusive
+    /// range of values returned by `knot_domain`. Derivatives of an order higher than the
+    /// curve's degree are zero.
+    pub fn derivative(&self, t: F, order: usize) -> T {
+        debug_assert!(t >= self.knot_domain().0 && t <= self.knot_domain().1);
+        let i = self.knot_span(t);
+        let points = &self.control_points[i - self.degree - 1..i];
+        if order > self.degree {
+            return points[0].scale(F::zero());
+        }
+        with_scratch(points, |tmp| {
+            de_boor_derivative(tmp, &self.knots, self.degree, t, i, order)
+        })
+    }
+    /// Compute the derivative of the curve as a new B-spline of degree `degree - 1`, also known
+    /// as the hodograph of the curve. The derivative curve has one less control point than
+    /// this curve and its knot vector drops the first and last knots, so it is defined over the
+    /// same `knot_domain`. The derivative of a degree 0 curve is zero everywhere and is returned
+    /// as a degree 0 curve with zero control points.
+    pub fn derivative_curve(&self) -> BSpline<T, F> {
+        if self.degree == 0 {
+            let zero = self.control_points[0].scale(F::zero());
+            return BSpline {
+                degree: 0,
+                control_points: vec![zero; self.control_points.len()],
+                knots: self.knots.clone(),
+            };
         }
-        tmp[0]
+        let p = self.degree;
+        let control_points = self
+            .control_points
+            .windows(2)
+            .enumerate()
+            .map(|(i, pts)| {
+                let d = self.knots[i + p + 1] - self.knots[i + 1];
+                let s = if d > F::zero() {
+                    from_usize::<F>(p) / d
+                } else {
+                    F::zero()
+                };
+                pts[1].difference(&pts[0]).scale(s)
+            })
+            .collect();
+        BSpline {
+            degree: p - 1,
+            control_points,
+            knots: self.knots[1..self.knots.len() - 1].to_vec(),
+        }
+    }
+}
+
+/// The number of control points that can be copied to the stack for evaluating the curve,
+/// curves with a degree less than this are evaluated without any heap allocations.
+const STACK_SCRATCH_LEN: usize = 16;
+
+/// Copy `points` into a scratch buffer and call `f` with the buffer. The buffer is on the stack
+/// if there are at most `STACK_SCRATCH_LEN` points, otherwise it is allocated on the heap.
+fn with_scratch<T: Copy, R, G: FnOnce(&mut [T]) -> R>(points: &[T], f: G) -> R {
+    if points.len() <= STACK_SCRATCH_LEN {
+        let mut tmp = [points[0]; STACK_SCRATCH_LEN];
+        tmp[..points.len()].copy_from_slice(points);
+        f(&mut tmp[..points.len()])
+    } else {
+        f(&mut points.to_vec())
+    }
+}
+
+/// Iterator sampling a B-spline curve with a fixed step size over its domain, created by
+/// `BSpline::sample_iter`. Returns `(t, point)` pairs.
+#[derive(Clone, Debug)]
+pub struct SampleIter<'a, T: Interpolate<F> + Copy, F: Float> {
+    spline: &'a BSpline<T, F>,
+    step: F,
+    sample: usize,
+    steps: usize,
+    span: usize,
+}
+
+impl<'a, T: Interpolate<F> + Copy, F: Float> Iterator for SampleIter<'a, T, F> {
+    type Item = (F, T);
+
+    fn next(&mut self) -> Option<(F, T)> {
+        if self.sample > self.steps {
+            return None;
+        }
+        let (min, max) = self.spline.knot_domain();
+        let mut t = if self.sample == 0 {
+            min
+        } else {
+            self.step * from_usize(self.sample) + min
+        };
+        if t > max {
+            t = max;
+        }
+        self.sample += 1;
+        self.span = self.spline.next_knot_span(t, self.span);
+        Some((t, self.spline.de_boor_iterative(t, self.span)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.steps + 1).saturating_sub(self.sample);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T: Interpolate<F> + Copy, F: Float> ExactSizeIterator for SampleIter<'a, T, F> {}
+
+/// Compute de Boor's algorithm on the `degree + 1` control points in `tmp` that influence
+/// the knot span `i_start`, such that `knots[i_start - 1] <= t < knots[i_start]`. The points
+/// are overwritten as the levels of the recursion are computed and the point on the curve is
+/// returned.
+fn de_boor<T: Interpolate<F> + Copy, F: Float>(
+    tmp: &mut [T],
+    knots: &[F],
+    degree: usize,
+    t: F,
+    i_start: usize,
+) -> T {
+    for lvl in 0..degree {
+        let k = lvl + 1;
+        for j in 0..degree - lvl {
+            let i = j + k + i_start - degree;
+            let alpha = (t - knots[i - 1]) / (knots[i + degree - k] - knots[i - 1]);
+            // A non-finite t is passed through by point_clamped and point_extrapolated
+            debug_assert!(alpha.is_finite() || !t.is_finite());
+            tmp[j] = tmp[j].interpolate(&tmp[j + 1], alpha);
+        }
+    }
+    tmp[0]
+}
+
+/// Replace the `degree + 1` control points in `tmp` influencing the knot span `i_start` with
+/// the control points of the `order`-th derivative curve influencing the same span. After
+/// this the first `degree + 1 - order` entries of `tmp` hold the derivative control points.
+/// Where the knot spacing is zero the derivative control point has no influence on the curve
+/// and is set to zero.
+fn differentiate<T: VectorSpace<F>, F: Float>(
+    tmp: &mut [T],
+    knots: &[F],
+    degree: usize,
+    i_start: usize,
+    order: usize,
+) {
+    for k in 1..=order {
+        let scale = from_usize::<F>(degree - k + 1);
+        for j in 0..=degree - k {
+            let g = j + i_start - degree - 1;
+            let d = knots[g + degree + 1] - knots[g + k];
+            let s = if d > F::zero() { scale / d } else { F::zero() };
+            tmp[j] = tmp[j + 1].difference(&tmp[j]).scale(s);
+        }
+    }
+}
+
+/// Compute the `order`-th derivative at `t` of the curve piece defined by the `degree + 1`
+/// control points in `tmp` that influence the knot span `i_start`, overwriting the points.
+/// The `order` must be at most `degree`.
+fn de_boor_derivative<T: VectorSpace<F>, F: Float>(
+    tmp: &mut [T],
+    knots: &[F],
+    degree: usize,
+    t: F,
+    i_start: usize,
+    order: usize,
+) -> T {
+    differentiate(tmp, knots, degree, i_start, order);
+    // The derivative is a B-spline of degree `degree - order` on the knot vector with
+    // `order` knots dropped from each end, so the same de Boor evaluation applies.
+    de_boor(
+        &mut tmp[..=degree - order],
+        &knots[order..knots.len() - order],
+        degree - order,
+        t,
+        i_start - order,
+    )
+}
+
+/// Check that `knots` is a valid knot vector for a curve of `degree` with `count` control
+/// points, with the same requirements as `BSpline::try_new`.
+fn check_knots<F: Float>(degree: usize, count: usize, knots: &[F]) -> Result<(), BSplineError> {
+    if count <= degree {
+        return Err(BSplineError::TooFewControlPoints {
+            degree,
+            found: count,
+        });
+    }
+    if knots.len() != count + degree + 1 {
+        return Err(BSplineError::InvalidKnotCount {
+            expected: count + degree + 1,
+            found: knots.len(),
+        });
+    }
+    if let Some(index) = knots.iter().position(|k| !k.is_finite()) {
+        return Err(BSplineError::NonFiniteKnot { index });
+    }
+    if let Some(index) = (1..knots.len()).find(|&i| knots[i] < knots[i - 1]) {
+        return Err(BSplineError::UnsortedKnots { index });
+    }
+    check_knot_multiplicity(knots, degree)?;
+    if knots[degree] == knots[knots.len() - 1 - degree] {
+        return Err(BSplineError::EmptyKnotDomain);
+    }
+    Ok(())
+}
+
+/// Check that no value in the sorted `knots` is repeated more than `degree + 1` times.
+fn check_knot_multiplicity<F: Float>(knots: &[F], degree: usize) -> Result<(), BSplineError> {
+    let mut index = 0;
+    while index < knots.len() {
+        let multiplicity = knots[index..]
+            .iter()
+            .take_while(|&&k| k == knots[index])
+            .count();
+        if multiplicity > degree + 1 {
+            return Err(BSplineError::KnotMultiplicityTooHigh {
+                index,
+                multiplicity,
+                max: degree + 1,
+            });
+        }
+        index += multiplicity;
+    }
+    Ok(())
+}
+
+/// Compute the binomial coefficient `n` choose `k`.
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k.min(n - k)).fold(1, |c, i| c * (n - i) / (i + 1))
+}
+
+/// Convert a count or index to the float type used by the curve.
+#[cfg(not(feature = "nalgebra-support"))]
+fn from_usize<F: Float>(x: usize) -> F {
+    <F as num_traits::NumCast>::from(x).unwrap()
+}
+
+/// Convert a count or index to the float type used by the curve.
+#[cfg(feature = "nalgebra-support")]
+fn from_usize<F: Float>(x: usize) -> F {
+    nalgebra::convert(x as f64)
+}
+
+/// Convert a non-negative float to a count, truncating any fractional part.
+#[cfg(not(feature = "nalgebra-support"))]
+fn to_usize<F: Float>(x: F) -> usize {
+    num_traits::ToPrimitive::to_usize(&x).unwrap_or(0)
+}
+
+/// Convert a non-negative float to a count, truncating any fractional part.
+#[cfg(feature = "nalgebra-support")]
+fn to_usize<F: Float>(x: F) -> usize {
+    nalgebra::try_convert::<F, f64>(x).unwrap_or(0.0) as usize
+}
+
+/// Find the knot span of `t` in the knot vector of a curve of `degree`, see
+/// `BSpline::knot_span`.
+fn knot_span<F: Float>(knots: &[F], degree: usize, t: F) -> usize {
+    let first = degree + 1;
+    let last = knots.len() - degree - 1;
+    match upper_bounds(knots, t) {
+        Some(x) if x < first => (first..=last)
+            .find(|&i| knots[i - 1] < knots[i])
+            .unwrap_or(first),
+        Some(x) if x <= last => x,
+        _ => (first..=last)
+            .rev()
+            .find(|&i| knots[i - 1] < knots[i])
+            .unwrap_or(last),
     }
 }
 
@@ -0,0 +1,139 @@
+use crate::{BSplineError, Float, VectorSpace};
+
+/// A square banded matrix with `lower` diagonals below the main diagonal and `upper` above
+/// it, used for the linear systems solved when fitting curves. Each row only stores the
+/// entries within the band, with room for the extra `lower` diagonals above the band that
+/// row swaps can fill in while solving.
+#[derive(Clone, Debug)]
+pub(crate) struct BandMatrix<F> {
+    size: usize,
+    lower: usize,
+    upper: usize,
+    data: Vec<F>,
+}
+
+impl<F: Float> BandMatrix<F> {
+    /// Create a `size` by `size` matrix of zeros with the given band
+    pub(crate) fn new(size: usize, lower: usize, upper: usize) -> BandMatrix<F> {
+        BandMatrix {
+            size,
+            lower,
+            upper,
+            data: vec![F::zero(); size * (2 * lower + upper + 1)],
+        }
+    }
+    /// Get the entry at row `i` and column `j`, which is zero outside the band
+    pub(crate) fn get(&self, i: usize, j: usize) -> F {
+        if j + self.lower < i || j > i + self.upper {
+            F::zero()
+        } else {
+            self.data[self.index(i, j)]
+        }
+    }
+    /// Add `x` to the entry at row `i` and column `j`, which must be within the band
+    pub(crate) fn add(&mut self, i: usize, j: usize, x: F) {
+        assert!(j + self.lower >= i && j <= i + self.upper);
+        let idx = self.index(i, j);
+        self.data[idx] = self.data[idx] + x;
+    }
+    /// Add `other` scaled by `s` to this matrix, the matrices must have the same size and band
+    pub(crate) fn add_scaled(&mut self, other: &BandMatrix<F>, s: F) {
+        assert!(self.size == other.size && self.lower == other.lower && self.upper == other.upper);
+        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a = *a + *b * s;
+        }
+    }
+    /// Solve `A x = b` in place, replacing `rhs` with the solution `x`. Returns an error if
+    /// the matrix is singular.
+    pub(crate) fn solve<T: VectorSpace<F>>(self, rhs: &mut [T]) -> Result<(), BSplineError> {
+        self.factor()?.solve(rhs);
+        Ok(())
+    }
+    /// Compute the LU factorization of the matrix with Gaussian elimination with partial
+    /// pivoting, so systems with the same matrix can be solved repeatedly. Returns an error
+    /// if the matrix is singular.
+    pub(crate) fn factor(mut self) -> Result<BandLu<F>, BSplineError> {
+        let n = self.size;
+        let reach = self.lower + self.upper;
+        let mut pivots = Vec::with_capacity(n);
+        for k in 0..n {
+            let last_row = (k + self.lower).min(n - 1);
+            let last_col = (k + reach).min(n - 1);
+            let pivot = (k..=last_row)
+                .max_by(|&a, &b| {
+                    let a = self.get(a, k).abs();
+                    let b = self.get(b, k).abs();
+                    a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap();
+            let a_kk = self.get(pivot, k);
+            if a_kk == F::zero() || !a_kk.is_finite() {
+                return Err(BSplineError::SingularSystem);
+            }
+            if pivot != k {
+                for j in k..=last_col {
+                    let a = self.index(k, j);
+                    let b = self.index(pivot, j);
+                    self.data.swap(a, b);
+                }
+            }
+            pivots.push(pivot);
+            // Eliminate the column below the pivot, storing the multipliers in its place
+            for i in k + 1..=last_row {
+                let f = self.get(i, k) / a_kk;
+                let idx = self.index(i, k);
+                self.data[idx] = f;
+                if f == F::zero() {
+                    continue;
+                }
+                for j in k + 1..=last_col {
+                    let idx = self.index(i, j);
+                    self.data[idx] = self.data[idx] - f * self.data[self.index(k, j)];
+                }
+            }
+        }
+        Ok(BandLu {
+            matrix: self,
+            pivots,
+        })
+    }
+    /// Find the index in `data` of the entry at row `i` and column `j`, including the
+    /// entries above the band filled in by `factor`
+    fn index(&self, i: usize, j: usize) -> usize {
+        i * (2 * self.lower + self.upper + 1) + j + self.lower - i
+    }
+}
+
+/// The LU factorization of a `BandMatrix`, storing the row swaps made for pivoting along with
+/// the upper triangular factor and the multipliers of the unit lower triangular factor.
+#[derive(Clone, Debug)]
+pub(crate) struct BandLu<F> {
+    matrix: BandMatrix<F>,
+    pivots: Vec<usize>,
+}
+
+impl<F: Float> BandLu<F> {
+    /// Solve `A x = b` in place, replacing `rhs` with the solution `x`
+    pub(crate) fn solve<T: VectorSpace<F>>(&self, rhs: &mut [T]) {
+        let a = &self.matrix;
+        let n = a.size;
+        let reach = a.lower + a.upper;
+        for k in 0..n {
+            rhs.swap(k, self.pivots[k]);
+            for i in k + 1..=(k + a.lower).min(n - 1) {
+                let f = a.data[a.index(i, k)];
+                if f != F::zero() {
+                    rhs[i] = rhs[i].difference(&rhs[k].scale(f));
+                }
+            }
+        }
+        for k in (0..n).rev() {
+            let mut x = rhs[k];
+            let last_col = (k + reach).min(n - 1);
+            for (j, r) in rhs.iter().enumerate().take(last_col + 1).skip(k + 1) {
+                x = x.difference(&r.scale(a.data[a.index(k, j)]));
+            }
+            rhs[k] = x.scale(F::one() / a.data[a.index(k, k)]);
+        }
+    }
+}
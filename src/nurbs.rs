@@ -0,0 +1,197 @@
+use std::ops::{Add, Mul};
+use std::slice::Iter;
+
+use crate::{binomial, BSpline, BSplineError, Float, VectorSpace};
+
+/// A control point of a rational curve in homogeneous coordinates, storing the point
+/// premultiplied by its weight along with the weight. Rational curves are evaluated by
+/// running the polynomial B-spline algorithms on these points and projecting the result.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Homogeneous<T, F> {
+    pub(crate) point: T,
+    pub(crate) weight: F,
+}
+
+impl<T: VectorSpace<F>, F: Float> Homogeneous<T, F> {
+    /// Lift `point` with `weight` into homogeneous coordinates
+    pub(crate) fn new(point: T, weight: F) -> Homogeneous<T, F> {
+        Homogeneous {
+            point: point.scale(weight),
+            weight,
+        }
+    }
+    /// Project the point back out of homogeneous coordinates
+    pub(crate) fn project(&self) -> T {
+        self.point.scale(F::one() / self.weight)
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> Mul<F> for Homogeneous<T, F> {
+    type Output = Homogeneous<T, F>;
+    fn mul(self, rhs: F) -> Homogeneous<T, F> {
+        Homogeneous {
+            point: self.point.scale(rhs),
+            weight: self.weight * rhs,
+        }
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> Add for Homogeneous<T, F> {
+    type Output = Homogeneous<T, F>;
+    fn add(self, rhs: Homogeneous<T, F>) -> Homogeneous<T, F> {
+        Homogeneous {
+            point: self.point.sum(&rhs.point),
+            weight: self.weight + rhs.weight,
+        }
+    }
+}
+
+/// Check that there is one finite, positive weight per control point
+pub(crate) fn validate_weights<F: Float>(
+    weights: &[F],
+    control_points: usize,
+) -> Result<(), BSplineError> {
+    if weights.len() != control_points {
+        return Err(BSplineError::InvalidWeightCount {
+            expected: control_points,
+            found: weights.len(),
+        });
+    }
+    match weights
+        .iter()
+        .position(|w| !w.is_finite() || *w <= F::zero())
+    {
+        Some(index) => Err(BSplineError::InvalidWeight { index }),
+        None => Ok(()),
+    }
+}
+
+/// Represents a rational B-spline curve (NURBS), where each control point has a weight
+/// controlling how strongly it pulls the curve towards it. Unlike `BSpline` this can
+/// exactly represent conic sections such as circles and ellipses.
+///
+/// The curve is evaluated in homogeneous space with the same de Boor algorithm used by
+/// `BSpline` and projected back, so the control points must support the `VectorSpace`
+/// operations to be weighted.
+#[derive(Clone, Debug)]
+pub struct NurbsCurve<T: VectorSpace<F>, F: Float> {
+    /// The polynomial curve in homogeneous coordinates
+    curve: BSpline<Homogeneous<T, F>, F>,
+}
+
+impl<T: VectorSpace<F>, F: Float> NurbsCurve<T, F> {
+    /// Create a new rational B-spline curve of the desired `degree` that will interpolate
+    /// the `control_points` using the `knots`, with each control point weighted by the
+    /// corresponding entry of `weights`. The requirements on the degree, control points and
+    /// knots are the same as `BSpline::new` and there must be one finite, positive weight per
+    /// control point, otherwise the function will panic.
+    pub fn new(
+        degree: usize,
+        control_points: Vec<T>,
+        weights: Vec<F>,
+        knots: Vec<F>,
+    ) -> NurbsCurve<T, F> {
+        if let Err(e) = validate_weights(&weights, control_points.len()) {
+            panic!("{}", e);
+        }
+        let points = control_points
+            .iter()
+            .zip(weights.iter())
+            .map(|(p, w)| Homogeneous::new(*p, *w))
+            .collect();
+        NurbsCurve {
+            curve: BSpline::new(degree, points, knots),
+        }
+    }
+    /// Create a new rational B-spline curve, returning an error instead of panicking if the
+    /// parameters don't describe a valid curve. The degree, control points and knots are
+    /// validated as in `BSpline::try_new` and the weights must be finite, positive and
+    /// match the number of control points.
+    pub fn try_new(
+        degree: usize,
+        control_points: Vec<T>,
+        weights: Vec<F>,
+        knots: Vec<F>,
+    ) -> Result<NurbsCurve<T, F>, BSplineError> {
+        validate_weights(&weights, control_points.len())?;
+        let points = control_points
+            .iter()
+            .zip(weights.iter())
+            .map(|(p, w)| Homogeneous::new(*p, *w))
+            .collect();
+        Ok(NurbsCurve {
+            curve: BSpline::try_new(degree, points, knots)?,
+        })
+    }
+    /// Compute a point on the curve at `t`, the parameter **must** be in the inclusive range
+    /// of values returned by `knot_domain`.
+    pub fn point(&self, t: F) -> T {
+        self.curve.point(t).project()
+    }
+    /// Compute the `order`-th derivative of the curve at `t`, where an `order` of 0 is the
+    /// point on the curve itself. The parameter **must** be in the inclusive range of values
+    /// returned by `knot_domain`.
+    ///
+    /// Unlike a polynomial curve the derivatives of a rational curve don't vanish above the
+    /// degree of the curve. They are computed from the derivatives of the curve in homogeneous
+    /// space, `A(t)` and `w(t)`, with the quotient rule:
+    ///
+    /// ```text
+    /// C^(k) = (A^(k) - sum_{i = 1}^{k} binomial(k, i) w^(i) C^(k - i)) / w
+    /// ```
+    pub fn derivative(&self, t: F, order: usize) -> T {
+        let homogeneous: Vec<Homogeneous<T, F>> =
+            (0..=order).map(|k| self.curve.derivative(t, k)).collect();
+        let w = homogeneous[0].weight;
+        let mut derivs: Vec<T> = Vec::with_capacity(order + 1);
+        for k in 0..=order {
+            let mut d = homogeneous[k].point;
+            for i in 1..=k {
+                let s = crate::from_usize::<F>(binomial(k, i)) * homogeneous[i].weight;
+                d = d.difference(&derivs[k - i].scale(s));
+            }
+            derivs.push(d.scale(F::one() / w));
+        }
+        derivs[order]
+    }
+    /// Get the degree of the polynomials used by the curve.
+    pub fn degree(&self) -> usize {
+        self.curve.degree
+    }
+    /// Get an iterator over the control points.
+    pub fn control_points(&self) -> impl Iterator<Item = T> + '_ {
+        self.curve.control_points.iter().map(|p| p.project())
+    }
+    /// Get an iterator over the weights of the control points.
+    pub fn weights(&self) -> impl Iterator<Item = F> + '_ {
+        self.curve.control_points.iter().map(|p| p.weight)
+    }
+    /// Get an iterator over the knots.
+    pub fn knots(&self) -> Iter<'_, F> {
+        self.curve.knots()
+    }
+    /// Get the min and max knot domain values for finding the `t` range to compute
+    /// the curve over. The curve is only defined over the inclusive range `[min, max]`.
+    pub fn knot_domain(&self) -> (F, F) {
+        self.curve.knot_domain()
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> From<BSpline<T, F>> for NurbsCurve<T, F> {
+    /// Convert a polynomial B-spline to a rational one with all weights set to one,
+    /// which describes the same curve.
+    fn from(spline: BSpline<T, F>) -> NurbsCurve<T, F> {
+        let points = spline
+            .control_points
+            .iter()
+            .map(|p| Homogeneous::new(*p, F::one()))
+            .collect();
+        NurbsCurve {
+            curve: BSpline {
+                degree: spline.degree,
+                control_points: points,
+                knots: spline.knots,
+            },
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{from_usize, BSpline, BSplineError, Float, Interpolate, KnotVector, VectorSpace};
+
+/// Represents a closed B-spline curve, which loops back around to its start with the same
+/// continuity at the seam as everywhere else. The curve is defined by a loop of control
+/// points and repeats with a period equal to the length of its `knot_domain`, so it can be
+/// evaluated at any `t` with the parameter wrapping around into the domain.
+///
+/// Internally this is a regular `BSpline` with the first `degree` control points repeated at
+/// the end and a periodic knot vector, which is available through `as_bspline` to use the
+/// rest of the B-spline operations on a single period of the curve.
+#[derive(Clone, Debug)]
+pub struct PeriodicBSpline<T: Interpolate<F> + Copy, F: Float> {
+    /// The curve over one period, with the wrapped control points and knots
+    curve: BSpline<T, F>,
+}
+
+impl<T: Interpolate<F> + Copy, F: Float> PeriodicBSpline<T, F> {
+    /// Create a closed B-spline curve of the desired `degree` that loops through the
+    /// `control_points` with uniformly spaced knots, as generated by `KnotVector::periodic`.
+    /// The knot domain of the curve is `[0, control_points.len()]` and the curve is `degree - 1`
+    /// times continuously differentiable everywhere, including where it joins back to its start.
+    ///
+    /// There must be more control points than the degree, otherwise the function will panic.
+    pub fn new(degree: usize, control_points: Vec<T>) -> PeriodicBSpline<T, F> {
+        let knots = KnotVector::periodic(degree, control_points.len());
+        PeriodicBSpline {
+            curve: BSpline::with_knot_vector(wrap(degree, control_points), knots),
+        }
+    }
+    /// Create a closed B-spline curve of the desired `degree` that loops through the
+    /// `control_points` with the knots of one period given by `knots`. There must be one more
+    /// knot than control points, the first and last knots are the ends of the `knot_domain`
+    /// and the knots are repeated periodically beyond them.
+    ///
+    /// The requirements on the knots are the same as `BSpline::try_new` and there must be more
+    /// control points than the degree, otherwise an error is returned.
+    pub fn with_knots(
+        degree: usize,
+        control_points: Vec<T>,
+        knots: Vec<F>,
+    ) -> Result<PeriodicBSpline<T, F>, BSplineError> {
+        let n = control_points.len();
+        if n <= degree {
+            return Err(BSplineError::TooFewControlPoints { degree, found: n });
+        }
+        if knots.len() != n + 1 {
+            return Err(BSplineError::InvalidKnotCount {
+                expected: n + 1,
+                found: knots.len(),
+            });
+        }
+        if let Some(index) = knots.iter().position(|k| !k.is_finite()) {
+            return Err(BSplineError::NonFiniteKnot { index });
+        }
+        if let Some(index) = (1..knots.len()).find(|&i| knots[i] < knots[i - 1]) {
+            return Err(BSplineError::UnsortedKnots { index });
+        }
+        let period = knots[n] - knots[0];
+        let mut full = Vec::with_capacity(n + 2 * degree + 1);
+        full.extend(knots[n - degree..n].iter().map(|&k| k - period));
+        full.extend_from_slice(&knots);
+        full.extend(knots[1..=degree].iter().map(|&k| k + period));
+        // Report errors with the index of the knot passed in instead of the extended knots
+        let curve =
+            BSpline::try_new(degree, wrap(degree, control_points), full).map_err(|e| match e {
+                BSplineError::KnotMultiplicityTooHigh {
+                    index,
+                    multiplicity,
+                    max,
+                } => BSplineError::KnotMultiplicityTooHigh {
+                    index: index.saturating_sub(degree),
+                    multiplicity,
+                    max,
+                },
+                e => e,
+            })?;
+        Ok(PeriodicBSpline { curve })
+    }
+    /// Compute a point on the curve at `t`, which can be any finite value as the parameter
+    /// wraps around into the `knot_domain`.
+    pub fn point(&self, t: F) -> T {
+        self.curve.point(self.wrap_parameter(t))
+    }
+    /// Sample `n` points on the curve at evenly spaced values of `t` across one period of the
+    /// curve, starting at the start of the `knot_domain`. The end of the domain isn't sampled
+    /// as it's the same point as the start.
+    pub fn sample(&self, n: usize) -> Vec<T> {
+        let (min, _) = self.knot_domain();
+        let step = self.period() / from_usize(n.max(1));
+        (0..n)
+            .map(|i| self.curve.point(min + step * from_usize(i)))
+            .collect()
+    }
+    /// Get the degree of the polynomials used by the curve.
+    pub fn degree(&self) -> usize {
+        self.curve.degree
+    }
+    /// Get the control points of the loop, without the repeated points that close it.
+    pub fn control_points(&self) -> &[T] {
+        let n = self.curve.control_points.len() - self.curve.degree;
+        &self.curve.control_points[..n]
+    }
+    /// Get the min and max knot domain values making up one period of the curve.
+    pub fn knot_domain(&self) -> (F, F) {
+        self.curve.knot_domain()
+    }
+    /// Get the length of the parameter range after which the curve repeats.
+    pub fn period(&self) -> F {
+        let (min, max) = self.knot_domain();
+        max - min
+    }
+    /// Get the regular B-spline curve tracing one period of this curve over the same
+    /// `knot_domain`.
+    pub fn as_bspline(&self) -> &BSpline<T, F> {
+        &self.curve
+    }
+    /// Wrap `t` into the knot domain of the curve
+    fn wrap_parameter(&self, t: F) -> F {
+        let (min, max) = self.knot_domain();
+        let period = max - min;
+        let t = t - period * ((t - min) / period).floor();
+        // Rounding can leave t just outside the domain
+        if t < min {
+            min
+        } else if t > max {
+            max
+        } else {
+            t
+        }
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> PeriodicBSpline<T, F> {
+    /// Compute the `order`-th derivative of the curve at `t`, where an `order` of 0 is the
+    /// point on the curve itself. As with `point` the parameter wraps around into the
+    /// `knot_domain`.
+    pub fn derivative(&self, t: F, order: usize) -> T {
+        self.curve.derivative(self.wrap_parameter(t), order)
+    }
+}
+
+/// Repeat the first `degree` control points at the end to close the loop
+fn wrap<T: Copy>(degree: usize, mut control_points: Vec<T>) -> Vec<T> {
+    if control_points.len() > degree {
+        control_points.extend_from_within(..degree);
+    }
+    control_points
+}
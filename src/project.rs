@@ -0,0 +1,211 @@
+use crate::{binomial, from_usize, with_scratch, BSpline, Float, InnerProduct};
+
+/// Maximum number of times an interval is split in half while isolating the roots
+const MAX_DEPTH: usize = 48;
+/// Maximum number of iterations refining a root
+const MAX_ITERATIONS: usize = 64;
+
+impl<T: InnerProduct<F>, F: Float> BSpline<T, F> {
+    /// Find the point on the curve closest to `point`, returning its parameter `t` and the
+    /// point on the curve. When several points on the curve are equally close the one with the
+    /// smallest `t` is returned.
+    ///
+    /// The closest point is either an end of the curve or a point where the vector from it to
+    /// `point` is perpendicular to the curve. Within each Bézier segment of the curve, see
+    /// `to_bezier_segments`, the dot product of that vector with the curve's derivative is a
+    /// polynomial of degree `2 * degree - 1`. Its roots are isolated by subdividing its
+    /// Bernstein form until each interval has a single sign change or none, which finds all of
+    /// them without needing a starting guess, and then refined with Newton's method.
+    ///
+    /// Where the curve is discontinuous, at a knot repeated `degree + 1` times, the ends of
+    /// the pieces on both sides of the knot are considered. If the end of the piece before the
+    /// knot is closest it's returned along with the knot, even though evaluating the curve at
+    /// the knot gives the start of the piece after it.
+    pub fn closest_point(&self, point: T) -> (F, T) {
+        let (min, _) = self.knot_domain();
+        let mut best = (min, self.point(min));
+        let mut best_distance = distance_squared(&best.1, &point);
+        let mut consider = |t: F, p: T| {
+            let d = distance_squared(&p, &point);
+            if d < best_distance {
+                best = (t, p);
+                best_distance = d;
+            }
+        };
+        for segment in self.to_bezier_segments() {
+            let (a, b) = segment.interval();
+            let points = segment.control_points();
+            // The end control points are the ends of the segment, which differ from the ends
+            // of its neighbours where the curve is discontinuous
+            consider(a, points[0]);
+            if self.degree > 0 {
+                let coeffs = perpendicular_coefficients(points, &point);
+                let mut roots = Vec::new();
+                isolate_roots(
+                    &coeffs,
+                    coeffs.clone(),
+                    F::zero(),
+                    F::one(),
+                    MAX_DEPTH,
+                    &mut roots,
+                );
+                for s in roots {
+                    let t = a + (b - a) * s;
+                    consider(t, self.point(t));
+                }
+            }
+            consider(b, points[points.len() - 1]);
+        }
+        best
+    }
+}
+
+/// Compute the squared distance between `a` and `b`
+fn distance_squared<T: InnerProduct<F>, F: Float>(a: &T, b: &T) -> F {
+    let d = a.difference(b);
+    d.dot(&d)
+}
+
+/// Compute the Bernstein coefficients of `(B(s) - point) . B'(s)` for the Bézier curve `B`
+/// with control points `points` over `s` in `[0, 1]`, dropping the constant factor of the
+/// degree from the derivative. The product of Bernstein polynomials of degrees `p` and `q`
+/// has the coefficients
+///
+/// ```text
+/// c_k = sum_{i + j = k} binomial(p, i) binomial(q, j) / binomial(p + q, k) a_i b_j
+/// ```
+fn perpendicular_coefficients<T: InnerProduct<F>, F: Float>(points: &[T], point: &T) -> Vec<F> {
+    let p = points.len() - 1;
+    let q = p - 1;
+    let offsets: Vec<T> = points.iter().map(|x| x.difference(point)).collect();
+    let tangents: Vec<T> = points.windows(2).map(|w| w[1].difference(&w[0])).collect();
+    (0..=p + q)
+        .map(|k| {
+            let scale = F::one() / from_usize(binomial(p + q, k));
+            (k.saturating_sub(q)..=k.min(p)).fold(F::zero(), |c, i| {
+                let j = k - i;
+                let w = from_usize::<F>(binomial(p, i) * binomial(q, j)) * scale;
+                c + w * offsets[i].dot(&tangents[j])
+            })
+        })
+        .collect()
+}
+
+/// Find the roots in `(lo, hi)` of the polynomial with Bernstein coefficients `original` over
+/// `[0, 1]`, where `coeffs` are its coefficients over `[lo, hi]`. By the variation diminishing
+/// property the polynomial has no more roots in the interval than sign changes in `coeffs`,
+/// so intervals without sign changes are skipped and intervals with a single sign change
+/// between ends of opposite signs bracket exactly one root. Other intervals are split in half.
+fn isolate_roots<F: Float>(
+    original: &[F],
+    coeffs: Vec<F>,
+    lo: F,
+    hi: F,
+    depth: usize,
+    roots: &mut Vec<F>,
+) {
+    let mut signs = coeffs
+        .iter()
+        .filter(|&&c| c != F::zero())
+        .map(|&c| c > F::zero());
+    let first = match signs.next() {
+        Some(first) => first,
+        // The polynomial is zero over the whole interval, so any point in it is a root
+        None => {
+            roots.push(lo);
+            return;
+        }
+    };
+    let changes = signs
+        .fold((0, first), |(n, last), s| (n + usize::from(s != last), s))
+        .0;
+    if changes == 0 {
+        return;
+    }
+    let (c0, cn) = (coeffs[0], coeffs[coeffs.len() - 1]);
+    if changes == 1 && c0 * cn < F::zero() {
+        roots.push(refine_root(original, lo, hi));
+    } else if depth == 0 {
+        roots.push((lo + hi) / from_usize(2));
+    } else {
+        let mid = (lo + hi) / from_usize(2);
+        let (left, right) = subdivide(&coeffs);
+        // A root exactly at the midpoint is an end of both halves, where it's not counted
+        // as a sign change, so it's found here instead
+        let at_mid = left[left.len() - 1] == F::zero() || evaluate(original, mid).0 == F::zero();
+        isolate_roots(original, left, lo, mid, depth - 1, roots);
+        if at_mid {
+            roots.push(mid);
+        }
+        isolate_roots(original, right, mid, hi, depth - 1, roots);
+    }
+}
+
+/// Refine the root of the polynomial with Bernstein coefficients `coeffs` bracketed by
+/// `[lo, hi]` with Newton's method, falling back to bisection whenever a step leaves the
+/// bracket.
+fn refine_root<F: Float>(coeffs: &[F], mut lo: F, mut hi: F) -> F {
+    let lo_negative = evaluate(coeffs, lo).0 < F::zero();
+    let mut s = (lo + hi) / from_usize(2);
+    for _ in 0..MAX_ITERATIONS {
+        let (f, df) = evaluate(coeffs, s);
+        if f == F::zero() {
+            break;
+        }
+        if (f < F::zero()) == lo_negative {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        let newton = s - f / df;
+        let next = if newton > lo && newton < hi {
+            newton
+        } else {
+            (lo + hi) / from_usize(2)
+        };
+        if next == s {
+            break;
+        }
+        s = next;
+    }
+    s
+}
+
+/// Evaluate the polynomial with Bernstein coefficients `coeffs` over `[0, 1]` and its
+/// derivative at `s` with de Casteljau's algorithm.
+fn evaluate<F: Float>(coeffs: &[F], s: F) -> (F, F) {
+    let n = coeffs.len() - 1;
+    with_scratch(coeffs, |tmp| {
+        let mut derivative = F::zero();
+        for lvl in 1..=n {
+            if lvl == n {
+                derivative = from_usize::<F>(n) * (tmp[1] - tmp[0]);
+            }
+            for j in 0..=n - lvl {
+                tmp[j] = tmp[j] + (tmp[j + 1] - tmp[j]) * s;
+            }
+        }
+        (tmp[0], derivative)
+    })
+}
+
+/// Split the polynomial with Bernstein coefficients `coeffs` over `[0, 1]` at one half,
+/// returning its coefficients over each half.
+fn subdivide<F: Float>(coeffs: &[F]) -> (Vec<F>, Vec<F>) {
+    let n = coeffs.len() - 1;
+    let half = F::one() / from_usize(2);
+    let mut tmp = coeffs.to_vec();
+    let mut left = Vec::with_capacity(n + 1);
+    let mut right = Vec::with_capacity(n + 1);
+    left.push(tmp[0]);
+    right.push(tmp[n]);
+    for lvl in 1..=n {
+        for j in 0..=n - lvl {
+            tmp[j] = (tmp[j] + tmp[j + 1]) * half;
+        }
+        left.push(tmp[0]);
+        right.push(tmp[n - lvl]);
+    }
+    right.reverse();
+    (left, right)
+}
@@ -0,0 +1,45 @@
+use crate::{from_usize, Float};
+
+/// Maximum number of Newton iterations refining each node
+const MAX_ITERATIONS: usize = 64;
+
+/// Compute the nodes and weights of the `n` point Gauss-Legendre quadrature rule on
+/// `[-1, 1]`, which integrates polynomials up to degree `2n - 1` exactly. The nodes are the
+/// roots of the Legendre polynomial of degree `n`, found with Newton's method.
+pub(crate) fn gauss_legendre<F: Float>(n: usize) -> Vec<(F, F)> {
+    let one = F::one();
+    let two = one + one;
+    let pi = one.atan() * from_usize(4);
+    // Evaluate the Legendre polynomial and its derivative at x by recurrence
+    let legendre = |x: F| {
+        let (mut p0, mut p1) = (one, x);
+        for k in 2..=n {
+            let k = from_usize::<F>(k);
+            let p2 = ((two * k - one) * x * p1 - (k - one) * p0) / k;
+            p0 = p1;
+            p1 = p2;
+        }
+        (p1, from_usize::<F>(n) * (x * p1 - p0) / (x * x - one))
+    };
+    (0..n)
+        .map(|i| {
+            let guess = pi * (from_usize::<F>(i) + from_usize::<F>(3) / from_usize(4))
+                / (from_usize::<F>(n) + one / two);
+            let mut x = guess.cos();
+            // Newton's method converges quickly from the initial guess, so stop once the
+            // steps stop getting smaller as they've reached the precision of F
+            let mut last_step = one;
+            for _ in 0..MAX_ITERATIONS {
+                let (p, dp) = legendre(x);
+                let dx = p / dp;
+                if dx.abs() >= last_step {
+                    break;
+                }
+                x = x - dx;
+                last_step = dx.abs();
+            }
+            let (_, dp) = legendre(x);
+            (x, two / ((one - x * x) * dp * dp))
+        })
+        .collect()
+}
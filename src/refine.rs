@@ -0,0 +1,183 @@
+use crate::{check_knot_multiplicity, BSpline, BSplineError, Float, Interpolate};
+
+impl<T: Interpolate<F> + Copy, F: Float> BSpline<T, F> {
+    /// Insert the knot `u` into the curve `times` times using Boehm's algorithm, returning a
+    /// new curve with the refined knot vector and control points that traces exactly the same
+    /// shape. The knot must be within the curve's `knot_domain` and after insertion it can be
+    /// repeated at most `degree + 1` times, otherwise an error is returned.
+    pub fn insert_knot(&self, u: F, times: usize) -> Result<BSpline<T, F>, BSplineError> {
+        let (min, max) = self.knot_domain();
+        if !(u >= min && u <= max) {
+            return Err(BSplineError::ParameterOutOfDomain);
+        }
+        let multiplicity = self.knots.iter().filter(|&&k| k == u).count();
+        if multiplicity + times > self.degree + 1 {
+            return Err(BSplineError::KnotMultiplicityTooHigh {
+                index: self.knots.iter().position(|&k| k >= u).unwrap(),
+                multiplicity: multiplicity + times,
+                max: self.degree + 1,
+            });
+        }
+        let mut curve = self.clone();
+        for _ in 0..times {
+            curve = curve.insert_knot_once(u);
+        }
+        Ok(curve)
+    }
+    /// Insert all the knots in `new_knots` into the curve at once, returning a new curve with
+    /// the refined knot vector and control points that traces exactly the same shape. This is
+    /// more efficient than repeatedly calling `insert_knot` when inserting many knots, as each
+    /// control point is only moved once. The new knots must be sorted in non-decreasing order
+    /// and within the curve's `knot_domain`, and no knot can be repeated more than
+    /// `degree + 1` times in the refined knot vector, otherwise an error is returned.
+    pub fn refine_knots(&self, new_knots: &[F]) -> Result<BSpline<T, F>, BSplineError> {
+        if new_knots.is_empty() {
+            return Ok(self.clone());
+        }
+        if let Some(index) = new_knots.iter().position(|k| !k.is_finite()) {
+            return Err(BSplineError::NonFiniteKnot { index });
+        }
+        if let Some(index) = (1..new_knots.len()).find(|&i| new_knots[i] < new_knots[i - 1]) {
+            return Err(BSplineError::UnsortedKnots { index });
+        }
+        let (min, max) = self.knot_domain();
+        if !(new_knots[0] >= min && new_knots[new_knots.len() - 1] <= max) {
+            return Err(BSplineError::ParameterOutOfDomain);
+        }
+
+        // This follows algorithm A5.4 from The NURBS Book, using the same indexing: there are
+        // n + 1 control points, m + 1 knots and r + 1 new knots.
+        let p = self.degree;
+        let n = self.control_points.len() - 1;
+        let m = self.knots.len() - 1;
+        let r = new_knots.len() - 1;
+        let u = &self.knots;
+        let a = self.knot_span(new_knots[0]) - 1;
+        let b = self.knot_span(new_knots[r]);
+
+        let mut points = vec![self.control_points[0]; n + r + 2];
+        let mut knots = vec![u[0]; m + r + 2];
+        points[..=a - p].copy_from_slice(&self.control_points[..=a - p]);
+        points[b + r..].copy_from_slice(&self.control_points[b - 1..]);
+        knots[..=a].copy_from_slice(&u[..=a]);
+        knots[b + p + r + 1..].copy_from_slice(&u[b + p..]);
+
+        let mut i = b + p - 1;
+        let mut k = b + p + r;
+        for &x in new_knots.iter().rev() {
+            while x <= u[i] && i > a {
+                points[k - p - 1] = self.control_points[i - p - 1];
+                knots[k] = u[i];
+                k -= 1;
+                i -= 1;
+            }
+            points[k - p - 1] = points[k - p];
+            for l in 1..=p {
+                let ind = k - p + l;
+                let alpha = knots[k + l] - x;
+                if alpha == F::zero() {
+                    points[ind - 1] = points[ind];
+                } else {
+                    let alpha = alpha / (knots[k + l] - u[i - p + l]);
+                    points[ind - 1] = points[ind].interpolate(&points[ind - 1], alpha);
+                }
+            }
+            knots[k] = x;
+            k -= 1;
+        }
+        check_knot_multiplicity(&knots, p)?;
+        Ok(BSpline {
+            degree: p,
+            control_points: points,
+            knots,
+        })
+    }
+    /// Split the curve at `t` into two curves, the first covering `[min, t]` of the
+    /// `knot_domain` and the second covering `[t, max]`. Together the two curves trace exactly
+    /// the same shape as the original. The split point is found by inserting `t` until it is
+    /// repeated `degree + 1` times, which clamps both curves at `t`.
+    ///
+    /// `t` must be strictly inside the curve's `knot_domain`, otherwise the function will panic.
+    pub fn split(&self, t: F) -> (BSpline<T, F>, BSpline<T, F>) {
+        let (min, max) = self.knot_domain();
+        assert!(
+            t > min && t < max,
+            "Split parameter must be strictly inside the knot domain"
+        );
+        let (refined, r) = self.clamp_at(t);
+        refined.split_at_knot(r)
+    }
+    /// Extract the part of the curve over the parameter interval `[t0, t1]` as a new curve,
+    /// which traces exactly the same shape as this curve over the interval. The knot vector of
+    /// the new curve is clamped at both ends, so it starts and ends on its first and last
+    /// control points even if this curve's knot vector isn't clamped.
+    ///
+    /// The interval must be within the curve's `knot_domain` and `t0` must be less than `t1`,
+    /// otherwise the function will panic.
+    pub fn subcurve(&self, t0: F, t1: F) -> BSpline<T, F> {
+        let (min, max) = self.knot_domain();
+        assert!(
+            t0 >= min && t1 <= max && t0 < t1,
+            "Subcurve interval must be a non-empty interval within the knot domain"
+        );
+        let (refined, r) = self.clamp_at(t0);
+        let (_, right) = refined.split_at_knot(r);
+        let (refined, r) = right.clamp_at(t1);
+        let (left, _) = refined.split_at_knot(r);
+        left
+    }
+    /// Insert `t` into the knot vector until it is repeated `degree + 1` times, returning the
+    /// refined curve and the index of the first copy of `t` in its knot vector. `t` must be
+    /// within the curve's `knot_domain`.
+    fn clamp_at(&self, t: F) -> (BSpline<T, F>, usize) {
+        let multiplicity = self.knots.iter().filter(|&&k| k == t).count();
+        let times = (self.degree + 1).saturating_sub(multiplicity);
+        let refined = if times > 0 {
+            self.insert_knot(t, times).unwrap()
+        } else {
+            self.clone()
+        };
+        let r = refined.knots.iter().position(|&k| k == t).unwrap();
+        (refined, r)
+    }
+    /// Split a curve whose knot vector repeats a value `degree + 1` times starting at index
+    /// `r` into the curves before and after that knot.
+    fn split_at_knot(self, r: usize) -> (BSpline<T, F>, BSpline<T, F>) {
+        let p = self.degree;
+        let left = BSpline {
+            degree: p,
+            control_points: self.control_points[..r].to_vec(),
+            knots: self.knots[..r + p + 1].to_vec(),
+        };
+        let right = BSpline {
+            degree: p,
+            control_points: self.control_points[r..].to_vec(),
+            knots: self.knots[r..].to_vec(),
+        };
+        (left, right)
+    }
+    /// Insert the knot `u` once using Boehm's algorithm, the caller must check that `u` is in
+    /// the domain and can be inserted without exceeding the maximum multiplicity.
+    fn insert_knot_once(&self, u: F) -> BSpline<T, F> {
+        let p = self.degree;
+        // The span k such that knots[k] <= u <= knots[k + 1]
+        let k = self.knot_span(u) - 1;
+        let mut points = Vec::with_capacity(self.control_points.len() + 1);
+        points.extend_from_slice(&self.control_points[..=k - p]);
+        for i in k - p + 1..=k {
+            let alpha = (u - self.knots[i]) / (self.knots[i + p] - self.knots[i]);
+            points.push(self.control_points[i - 1].interpolate(&self.control_points[i], alpha));
+        }
+        points.extend_from_slice(&self.control_points[k..]);
+
+        let mut knots = Vec::with_capacity(self.knots.len() + 1);
+        knots.extend_from_slice(&self.knots[..=k]);
+        knots.push(u);
+        knots.extend_from_slice(&self.knots[k + 1..]);
+        BSpline {
+            degree: p,
+            control_points: points,
+            knots,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{BSpline, BSplineError, Float, InnerProduct};
+
+impl<T: InnerProduct<F>, F: Float> BSpline<T, F> {
+    /// Remove the interior knot `u` from the curve `times` times, returning a new curve with
+    /// fewer control points along with its deviation from this curve. The deviation is an
+    /// upper bound on the distance between the two curves at any parameter value, and if it's
+    /// greater than `tolerance` an error is returned instead. A knot can be removed exactly,
+    /// with a deviation of zero, if the curve is smooth enough at the knot, for example when
+    /// the knot was inserted with `insert_knot`.
+    ///
+    /// `u` must be strictly inside the curve's `knot_domain` and be repeated at least `times`
+    /// times in the knot vector, otherwise an error is returned.
+    pub fn remove_knot(
+        &self,
+        u: F,
+        times: usize,
+        tolerance: F,
+    ) -> Result<(BSpline<T, F>, F), BSplineError> {
+        let (min, max) = self.knot_domain();
+        if !(u > min && u < max) {
+            return Err(BSplineError::ParameterOutOfDomain);
+        }
+        let multiplicity = self.knots.iter().filter(|&&k| k == u).count();
+        if multiplicity < times {
+            return Err(BSplineError::KnotMultiplicityTooLow {
+                multiplicity,
+                times,
+            });
+        }
+        if times == 0 {
+            return Ok((self.clone(), F::zero()));
+        }
+        let curve = self.remove_knot_unchecked(u, times, multiplicity);
+        let deviation = curve.deviation_from(self);
+        if deviation > tolerance {
+            return Err(BSplineError::ToleranceExceeded);
+        }
+        Ok((curve, deviation))
+    }
+    /// Simplify the curve by removing as many of its interior knots as possible while keeping
+    /// it within `tolerance` of this curve, returning the simplified curve along with its
+    /// deviation from this curve. As with `remove_knot` the deviation is an upper bound on the
+    /// distance between the two curves at any parameter value.
+    ///
+    /// The knots are removed greedily one at a time, so this is useful for shrinking curves
+    /// with redundant knots, such as those produced by knot refinement, degree reduction or
+    /// fitting, but it won't necessarily find the curve with the fewest knots.
+    pub fn simplify(&self, tolerance: F) -> (BSpline<T, F>, F) {
+        let (min, max) = self.knot_domain();
+        let mut curve = self.clone();
+        let mut deviation = F::zero();
+        let mut removed = true;
+        while removed {
+            removed = false;
+            let mut i = self.degree + 1;
+            while i < curve.knots.len() - self.degree - 1 {
+                let u = curve.knots[i];
+                let multiplicity = curve.knots.iter().filter(|&&k| k == u).count();
+                if u > min && u < max {
+                    let candidate = curve.remove_knot_unchecked(u, 1, multiplicity);
+                    let d = candidate.deviation_from(self);
+                    if d <= tolerance {
+                        curve = candidate;
+                        deviation = d;
+                        removed = true;
+                        continue;
+                    }
+                }
+                i += 1;
+            }
+        }
+        (curve, deviation)
+    }
+    /// Remove the interior knot `u` which is repeated `multiplicity` times in the knot vector
+    /// `times` times, without checking the deviation this causes.
+    ///
+    /// This follows algorithm A5.8 from The NURBS Book, using the same indexing: there are
+    /// n + 1 control points and the last copy of `u` is the knot r. The control points are
+    /// solved for from both ends of the affected range towards the middle, where the two
+    /// solutions are averaged if they don't agree.
+    pub(crate) fn remove_knot_unchecked(
+        &self,
+        u: F,
+        times: usize,
+        multiplicity: usize,
+    ) -> BSpline<T, F> {
+        let p = self.degree;
+        let s = multiplicity;
+        let n = self.control_points.len() - 1;
+        let r = self.knots.iter().rposition(|&k| k == u).unwrap();
+        let knots = &self.knots;
+        let mut pw = self.control_points.clone();
+        // The book sizes temp as 2p + 1, but removing a knot of multiplicity p + 1 all p + 1
+        // times writes to index 2p + 1
+        let mut temp = vec![pw[0]; 2 * p + 2];
+
+        for t in 0..times {
+            let first = r - p - t;
+            let last = r - s + t;
+            let off = first - 1;
+            temp[0] = pw[off];
+            temp[last + 1 - off] = pw[last + 1];
+            let mut i = first;
+            let mut j = last;
+            let mut ii = 1;
+            let mut jj = last - off;
+            while j > i + t {
+                let alfi = (u - knots[i]) / (knots[i + p + 1 + t] - knots[i]);
+                let alfj = (u - knots[j - t]) / (knots[j + p + 1] - knots[j - t]);
+                temp[ii] = pw[i]
+                    .difference(&temp[ii - 1].scale(F::one() - alfi))
+                    .scale(F::one() / alfi);
+                temp[jj] = pw[j]
+                    .difference(&temp[jj + 1].scale(alfj))
+                    .scale(F::one() / (F::one() - alfj));
+                i += 1;
+                ii += 1;
+                j -= 1;
+                jj -= 1;
+            }
+            if j < i + t {
+                let mid = temp[ii - 1].interpolate(&temp[jj + 1], F::one() / (F::one() + F::one()));
+                temp[ii - 1] = mid;
+                temp[jj + 1] = mid;
+            }
+            let mut i = first;
+            let mut j = last;
+            while j > i + t {
+                pw[i] = temp[i - off];
+                pw[j] = temp[j - off];
+                i += 1;
+                j -= 1;
+            }
+        }
+
+        let mut new_knots = knots.clone();
+        new_knots.drain(r + 1 - times..=r);
+        let fout = (2 * r - s - p) / 2;
+        let mut i = fout;
+        let mut j = fout;
+        for k in 1..times {
+            if k % 2 == 1 {
+                i += 1;
+            } else {
+                j -= 1;
+            }
+        }
+        for k in i + 1..=n {
+            pw[j] = pw[k];
+            j += 1;
+        }
+        pw.truncate(n + 1 - times);
+        BSpline {
+            degree: p,
+            control_points: pw,
+            knots: new_knots,
+        }
+    }
+    /// Compute an upper bound on the distance between this curve and `original`, where the
+    /// knots of this curve must be a subset of the knots of `original`. This curve is refined
+    /// to the knot vector of `original` so the curves can be compared by their control points.
+    pub(crate) fn deviation_from(&self, original: &BSpline<T, F>) -> F {
+        let mut missing = Vec::new();
+        let mut i = 0;
+        for &k in original.knots.iter() {
+            if i < self.knots.len() && self.knots[i] == k {
+                i += 1;
+            } else {
+                missing.push(k);
+            }
+        }
+        let refined = self.refine_knots(&missing).unwrap();
+        refined
+            .control_points
+            .iter()
+            .zip(original.control_points.iter())
+            .fold(F::zero(), |d, (a, b)| d.max(a.difference(b).norm()))
+    }
+}
@@ -0,0 +1,455 @@
+use std::slice::Iter;
+
+use crate::nurbs::{validate_weights, Homogeneous};
+use crate::{
+    binomial, check_knots, de_boor, de_boor_derivative, from_usize, knot_span, with_scratch,
+    BSplineError, Cross, Float, Interpolate, VectorSpace,
+};
+
+/// Represents a tensor product B-spline surface, which sweeps a B-spline curve in `v` along
+/// B-spline curves in `u`. The surface has a degree and knot vector in each direction and a
+/// grid of control points, where row `i` of the grid holds the control points of the curve in
+/// `v` weighted by the `i`-th basis function in `u`.
+///
+/// As with `BSpline` the control points can be of any type that can be linearly interpolated,
+/// the surface is evaluated by running de Boor's algorithm on each row of the grid in `v` and
+/// then on the resulting points in `u`.
+#[derive(Clone, Debug)]
+pub struct BSplineSurface<T: Interpolate<F> + Copy, F: Float> {
+    /// Degree of the surface in the `u` direction
+    degree_u: usize,
+    /// Degree of the surface in the `v` direction
+    degree_v: usize,
+    /// The control point grid stored row by row
+    control_points: Vec<T>,
+    /// Number of control points in each row of the grid
+    row_len: usize,
+    /// The knot vector in the `u` direction
+    knots_u: Vec<F>,
+    /// The knot vector in the `v` direction
+    knots_v: Vec<F>,
+}
+
+impl<T: Interpolate<F> + Copy, F: Float> BSplineSurface<T, F> {
+    /// Create a new B-spline surface of degree `degree_u` in `u` and `degree_v` in `v` that
+    /// will interpolate the grid of `control_points` using the knots in each direction. There
+    /// is one row of the grid per control point in `u` and every row must have one control
+    /// point per control point in `v`. As with `BSpline::new` the knots are sorted for you.
+    ///
+    /// Each direction must have a valid number of control points and knots as described in
+    /// `BSpline::new`, and all rows must be the same length, or the function will panic. Use
+    /// `try_new` to validate surfaces from untrusted input without panicking.
+    pub fn new(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<T>>,
+        mut knots_u: Vec<F>,
+        mut knots_v: Vec<F>,
+    ) -> BSplineSurface<T, F> {
+        let row_len = control_points.first().map_or(0, |r| r.len());
+        if control_points.len() <= degree_u || row_len <= degree_v {
+            panic!("Too few control points for surface");
+        }
+        if let Some(row) = control_points.iter().find(|r| r.len() != row_len) {
+            panic!(
+                "Invalid control point grid, got a row of {} points, expected {}",
+                row.len(),
+                row_len
+            );
+        }
+        if knots_u.len() != control_points.len() + degree_u + 1 {
+            panic!(
+                "Invalid number of knots in u, got {}, expected {}",
+                knots_u.len(),
+                control_points.len() + degree_u + 1
+            );
+        }
+        if knots_v.len() != row_len + degree_v + 1 {
+            panic!(
+                "Invalid number of knots in v, got {}, expected {}",
+                knots_v.len(),
+                row_len + degree_v + 1
+            );
+        }
+        knots_u.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        knots_v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        BSplineSurface {
+            degree_u,
+            degree_v,
+            control_points: control_points.concat(),
+            row_len,
+            knots_u,
+            knots_v,
+        }
+    }
+    /// Create a new B-spline surface, returning an error instead of panicking if the
+    /// parameters don't describe a valid surface. The knots in each direction are validated
+    /// as in `BSpline::try_new` and every row of the grid must be the same length.
+    pub fn try_new(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<T>>,
+        knots_u: Vec<F>,
+        knots_v: Vec<F>,
+    ) -> Result<BSplineSurface<T, F>, BSplineError> {
+        let row_len = control_points.first().map_or(0, |r| r.len());
+        if let Some(row) = control_points.iter().position(|r| r.len() != row_len) {
+            return Err(BSplineError::InvalidGridRow {
+                row,
+                expected: row_len,
+                found: control_points[row].len(),
+            });
+        }
+        check_knots(degree_u, control_points.len(), &knots_u)?;
+        check_knots(degree_v, row_len, &knots_v)?;
+        Ok(BSplineSurface {
+            degree_u,
+            degree_v,
+            control_points: control_points.concat(),
+            row_len,
+            knots_u,
+            knots_v,
+        })
+    }
+    /// Compute a point on the surface at `(u, v)`, the parameters **must** be in the
+    /// inclusive ranges of values returned by `knot_domain_u` and `knot_domain_v`. If they are
+    /// out of bounds this function will assert on debug builds.
+    ///
+    /// Surfaces with degrees less than 16 are evaluated entirely on the stack, so this never
+    /// allocates.
+    pub fn point(&self, u: F, v: F) -> T {
+        self.debug_assert_in_domain(u, v);
+        let span_u = knot_span(&self.knots_u, self.degree_u, u);
+        let span_v = knot_span(&self.knots_v, self.degree_v, v);
+        let first_v = span_v - self.degree_v - 1;
+        with_scratch(&self.control_points[..=self.degree_u], |column| {
+            for (i, c) in column.iter_mut().enumerate() {
+                let row = self.row(span_u - self.degree_u - 1 + i);
+                *c = with_scratch(&row[first_v..span_v], |tmp| {
+                    de_boor(tmp, &self.knots_v, self.degree_v, v, span_v)
+                });
+            }
+            de_boor(column, &self.knots_u, self.degree_u, u, span_u)
+        })
+    }
+    /// Get the degree of the surface in the `u` direction.
+    pub fn degree_u(&self) -> usize {
+        self.degree_u
+    }
+    /// Get the degree of the surface in the `v` direction.
+    pub fn degree_v(&self) -> usize {
+        self.degree_v
+    }
+    /// Get the number of control points in the `u` and `v` directions, the number of rows
+    /// in the grid and the length of each row.
+    pub fn control_point_count(&self) -> (usize, usize) {
+        (self.control_points.len() / self.row_len, self.row_len)
+    }
+    /// Get the control point in row `i` and column `j` of the grid.
+    pub fn control_point(&self, i: usize, j: usize) -> &T {
+        &self.row(i)[j]
+    }
+    /// Get an iterator over the control points, going through the grid row by row.
+    pub fn control_points(&self) -> Iter<'_, T> {
+        self.control_points.iter()
+    }
+    /// Get an iterator over the knots in the `u` direction.
+    pub fn knots_u(&self) -> Iter<'_, F> {
+        self.knots_u.iter()
+    }
+    /// Get an iterator over the knots in the `v` direction.
+    pub fn knots_v(&self) -> Iter<'_, F> {
+        self.knots_v.iter()
+    }
+    /// Get the min and max knot domain values in the `u` direction, the surface is only
+    /// defined for `u` in the inclusive range `[min, max]`.
+    pub fn knot_domain_u(&self) -> (F, F) {
+        (
+            self.knots_u[self.degree_u],
+            self.knots_u[self.knots_u.len() - 1 - self.degree_u],
+        )
+    }
+    /// Get the min and max knot domain values in the `v` direction, the surface is only
+    /// defined for `v` in the inclusive range `[min, max]`.
+    pub fn knot_domain_v(&self) -> (F, F) {
+        (
+            self.knots_v[self.degree_v],
+            self.knots_v[self.knots_v.len() - 1 - self.degree_v],
+        )
+    }
+    /// Get the parameter rectangle the surface is defined over, the knot domains in the `u`
+    /// and `v` directions as `((min_u, max_u), (min_v, max_v))`.
+    pub fn knot_domain(&self) -> ((F, F), (F, F)) {
+        (self.knot_domain_u(), self.knot_domain_v())
+    }
+    /// Get row `i` of the control point grid
+    fn row(&self, i: usize) -> &[T] {
+        &self.control_points[i * self.row_len..(i + 1) * self.row_len]
+    }
+    /// Check that `(u, v)` is within the knot domains on debug builds
+    fn debug_assert_in_domain(&self, u: F, v: F) {
+        debug_assert!(u >= self.knot_domain_u().0 && u <= self.knot_domain_u().1);
+        debug_assert!(v >= self.knot_domain_v().0 && v <= self.knot_domain_v().1);
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> BSplineSurface<T, F> {
+    /// Compute the partial derivative of the surface at `(u, v)`, differentiated `order_u`
+    /// times with respect to `u` and `order_v` times with respect to `v`. Orders of 0 in both
+    /// directions give the point on the surface itself. As with `point` the parameters
+    /// **must** be within the surface's knot domains. Derivatives of an order higher than the
+    /// surface's degree in either direction are zero.
+    pub fn derivative(&self, u: F, v: F, order_u: usize, order_v: usize) -> T {
+        self.debug_assert_in_domain(u, v);
+        if order_u > self.degree_u || order_v > self.degree_v {
+            return self.control_points[0].scale(F::zero());
+        }
+        let span_u = knot_span(&self.knots_u, self.degree_u, u);
+        let span_v = knot_span(&self.knots_v, self.degree_v, v);
+        let first_v = span_v - self.degree_v - 1;
+        with_scratch(&self.control_points[..=self.degree_u], |column| {
+            for (i, c) in column.iter_mut().enumerate() {
+                let row = self.row(span_u - self.degree_u - 1 + i);
+                *c = with_scratch(&row[first_v..span_v], |tmp| {
+                    de_boor_derivative(tmp, &self.knots_v, self.degree_v, v, span_v, order_v)
+                });
+            }
+            de_boor_derivative(column, &self.knots_u, self.degree_u, u, span_u, order_u)
+        })
+    }
+}
+
+impl<T: Cross<F>, F: Float> BSplineSurface<T, F> {
+    /// Compute the unit normal of the surface at `(u, v)`, the normalized cross product of the
+    /// first partial derivatives in `u` and `v`. Where the partial derivatives are parallel or
+    /// vanish, such as where a row of control points collapses to a single point, the normal
+    /// isn't defined and the zero vector is returned.
+    pub fn normal(&self, u: F, v: F) -> T {
+        unit_normal(&self.derivative(u, v, 1, 0), &self.derivative(u, v, 0, 1))
+    }
+}
+
+/// Normalize the cross product of the partial derivatives `du` and `dv`, returning the zero
+/// vector if it vanishes.
+fn unit_normal<T: Cross<F>, F: Float>(du: &T, dv: &T) -> T {
+    let n = du.cross(dv);
+    let length = n.norm();
+    if length > F::zero() {
+        n.scale(F::one() / length)
+    } else {
+        n
+    }
+}
+
+/// Represents a rational tensor product B-spline surface (NURBS), where each control point
+/// of the grid has a weight. Unlike `BSplineSurface` this can exactly represent quadric
+/// surfaces such as spheres, cylinders and tori, and it's the form surfaces are usually
+/// exchanged in by CAD software.
+///
+/// As with `NurbsCurve` the surface is evaluated in homogeneous space and projected back, so
+/// the control points must support the `VectorSpace` operations to be weighted.
+#[derive(Clone, Debug)]
+pub struct NurbsSurface<T: VectorSpace<F>, F: Float> {
+    /// The polynomial surface in homogeneous coordinates
+    surface: BSplineSurface<Homogeneous<T, F>, F>,
+}
+
+impl<T: VectorSpace<F>, F: Float> NurbsSurface<T, F> {
+    /// Create a new rational B-spline surface of degree `degree_u` in `u` and `degree_v` in
+    /// `v` that will interpolate the grid of `control_points` using the knots in each
+    /// direction, with each control point weighted by the corresponding entry of the grid of
+    /// `weights`. The requirements on the degrees, control points and knots are the same as
+    /// `BSplineSurface::new` and the weight grid must be the same shape as the control point
+    /// grid and hold finite, positive weights, otherwise the function will panic.
+    pub fn new(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<T>>,
+        weights: Vec<Vec<F>>,
+        knots_u: Vec<F>,
+        knots_v: Vec<F>,
+    ) -> NurbsSurface<T, F> {
+        let same_shape = weights.len() == control_points.len()
+            && weights
+                .iter()
+                .zip(control_points.iter())
+                .all(|(w, p)| w.len() == p.len());
+        if !same_shape {
+            panic!("The grid of weights must be the same shape as the grid of control points");
+        }
+        let flat = weights.concat();
+        if let Err(e) = validate_weights(&flat, flat.len()) {
+            panic!("{}", e);
+        }
+        NurbsSurface {
+            surface: BSplineSurface::new(
+                degree_u,
+                degree_v,
+                lift(control_points, &weights),
+                knots_u,
+                knots_v,
+            ),
+        }
+    }
+    /// Create a new rational B-spline surface, returning an error instead of panicking if the
+    /// parameters don't describe a valid surface. The degrees, control points and knots are
+    /// validated as in `BSplineSurface::try_new` and there must be one finite, positive weight
+    /// per control point. The index of an invalid weight counts through the grid row by row.
+    pub fn try_new(
+        degree_u: usize,
+        degree_v: usize,
+        control_points: Vec<Vec<T>>,
+        weights: Vec<Vec<F>>,
+        knots_u: Vec<F>,
+        knots_v: Vec<F>,
+    ) -> Result<NurbsSurface<T, F>, BSplineError> {
+        let row_len = control_points.first().map_or(0, |r| r.len());
+        if let Some(row) = control_points.iter().position(|r| r.len() != row_len) {
+            return Err(BSplineError::InvalidGridRow {
+                row,
+                expected: row_len,
+                found: control_points[row].len(),
+            });
+        }
+        let count = control_points.len() * row_len;
+        if weights.len() != control_points.len() || weights.iter().any(|w| w.len() != row_len) {
+            return Err(BSplineError::InvalidWeightCount {
+                expected: count,
+                found: weights.iter().map(|w| w.len()).sum(),
+            });
+        }
+        validate_weights(&weights.concat(), count)?;
+        Ok(NurbsSurface {
+            surface: BSplineSurface::try_new(
+                degree_u,
+                degree_v,
+                lift(control_points, &weights),
+                knots_u,
+                knots_v,
+            )?,
+        })
+    }
+    /// Compute a point on the surface at `(u, v)`, the parameters **must** be in the
+    /// parameter rectangle returned by `knot_domain`.
+    pub fn point(&self, u: F, v: F) -> T {
+        self.surface.point(u, v).project()
+    }
+    /// Compute the partial derivative of the surface at `(u, v)`, differentiated `order_u`
+    /// times with respect to `u` and `order_v` times with respect to `v`. The parameters
+    /// **must** be in the parameter rectangle returned by `knot_domain`.
+    ///
+    /// As with `NurbsCurve::derivative` the derivatives are found from the derivatives of the
+    /// surface in homogeneous space, `A` and `w`, with the quotient rule:
+    ///
+    /// ```text
+    /// S^(k, l) = (A^(k, l) - sum_{(i, j) != (0, 0)} binomial(k, i) binomial(l, j) w^(i, j) S^(k - i, l - j)) / w
+    /// ```
+    pub fn derivative(&self, u: F, v: F, order_u: usize, order_v: usize) -> T {
+        let cols = order_v + 1;
+        let homogeneous: Vec<Homogeneous<T, F>> = (0..=order_u)
+            .flat_map(|k| (0..=order_v).map(move |l| (k, l)))
+            .map(|(k, l)| self.surface.derivative(u, v, k, l))
+            .collect();
+        let w = homogeneous[0].weight;
+        let mut derivs: Vec<T> = Vec::with_capacity(homogeneous.len());
+        for k in 0..=order_u {
+            for l in 0..=order_v {
+                let mut d = homogeneous[k * cols + l].point;
+                for i in 0..=k {
+                    for j in (0..=l).filter(|&j| i + j > 0) {
+                        let s = from_usize::<F>(binomial(k, i) * binomial(l, j))
+                            * homogeneous[i * cols + j].weight;
+                        d = d.difference(&derivs[(k - i) * cols + l - j].scale(s));
+                    }
+                }
+                derivs.push(d.scale(F::one() / w));
+            }
+        }
+        derivs[order_u * cols + order_v]
+    }
+    /// Get the degree of the surface in the `u` direction.
+    pub fn degree_u(&self) -> usize {
+        self.surface.degree_u
+    }
+    /// Get the degree of the surface in the `v` direction.
+    pub fn degree_v(&self) -> usize {
+        self.surface.degree_v
+    }
+    /// Get the number of control points in the `u` and `v` directions, the number of rows
+    /// in the grid and the length of each row.
+    pub fn control_point_count(&self) -> (usize, usize) {
+        self.surface.control_point_count()
+    }
+    /// Get an iterator over the control points, going through the grid row by row.
+    pub fn control_points(&self) -> impl Iterator<Item = T> + '_ {
+        self.surface.control_points.iter().map(|p| p.project())
+    }
+    /// Get an iterator over the weights of the control points, going through the grid row by
+    /// row.
+    pub fn weights(&self) -> impl Iterator<Item = F> + '_ {
+        self.surface.control_points.iter().map(|p| p.weight)
+    }
+    /// Get an iterator over the knots in the `u` direction.
+    pub fn knots_u(&self) -> Iter<'_, F> {
+        self.surface.knots_u()
+    }
+    /// Get an iterator over the knots in the `v` direction.
+    pub fn knots_v(&self) -> Iter<'_, F> {
+        self.surface.knots_v()
+    }
+    /// Get the parameter rectangle the surface is defined over, the knot domains in the `u`
+    /// and `v` directions as `((min_u, max_u), (min_v, max_v))`.
+    pub fn knot_domain(&self) -> ((F, F), (F, F)) {
+        self.surface.knot_domain()
+    }
+}
+
+impl<T: Cross<F>, F: Float> NurbsSurface<T, F> {
+    /// Compute the unit normal of the surface at `(u, v)`, the normalized cross product of the
+    /// first partial derivatives in `u` and `v`. Where the partial derivatives are parallel or
+    /// vanish, such as at the poles of a sphere, the normal isn't defined and the zero vector
+    /// is returned.
+    pub fn normal(&self, u: F, v: F) -> T {
+        unit_normal(&self.derivative(u, v, 1, 0), &self.derivative(u, v, 0, 1))
+    }
+}
+
+impl<T: VectorSpace<F>, F: Float> From<BSplineSurface<T, F>> for NurbsSurface<T, F> {
+    /// Convert a polynomial B-spline surface to a rational one with all weights set to one,
+    /// which describes the same surface.
+    fn from(surface: BSplineSurface<T, F>) -> NurbsSurface<T, F> {
+        let control_points = surface
+            .control_points
+            .iter()
+            .map(|p| Homogeneous::new(*p, F::one()))
+            .collect();
+        NurbsSurface {
+            surface: BSplineSurface {
+                degree_u: surface.degree_u,
+                degree_v: surface.degree_v,
+                control_points,
+                row_len: surface.row_len,
+                knots_u: surface.knots_u,
+                knots_v: surface.knots_v,
+            },
+        }
+    }
+}
+
+/// Lift the grid of `control_points` into homogeneous coordinates with the grid of `weights`,
+/// which must be the same shape.
+fn lift<T: VectorSpace<F>, F: Float>(
+    control_points: Vec<Vec<T>>,
+    weights: &[Vec<F>],
+) -> Vec<Vec<Homogeneous<T, F>>> {
+    control_points
+        .into_iter()
+        .zip(weights.iter())
+        .map(|(row, w)| {
+            row.into_iter()
+                .zip(w.iter())
+                .map(|(p, w)| Homogeneous::new(p, *w))
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,391 @@
+use std::slice::Iter;
+
+use crate::{
+    check_knots, de_boor, from_usize, knot_span, with_scratch, BSplineError, Cross, Float,
+    Interpolate, KnotVector,
+};
+
+/// Represents a trivariate tensor product B-spline volume, which maps the parameters
+/// `(u, v, w)` through a three dimensional lattice of control points. The volume has a
+/// degree and knot vector in each direction, and slab `i` of the lattice holds a grid of
+/// control points like those of a `BSplineSurface` weighted by the `i`-th basis function in
+/// `u`.
+///
+/// The volume is evaluated by running de Boor's algorithm along each row of the lattice in
+/// `w`, then on the results in `v` and finally in `u`, so as with `BSpline` the control
+/// points can be of any type that can be linearly interpolated.
+#[derive(Clone, Debug)]
+pub struct BSplineVolume<T: Interpolate<F> + Copy, F: Float> {
+    /// Degree of the volume in the `u` direction
+    degree_u: usize,
+    /// Degree of the volume in the `v` direction
+    degree_v: usize,
+    /// Degree of the volume in the `w` direction
+    degree_w: usize,
+    /// The control point lattice stored slab by slab and row by row
+    control_points: Vec<T>,
+    /// Number of rows in each slab of the lattice
+    slab_len: usize,
+    /// Number of control points in each row of the lattice
+    row_len: usize,
+    /// The knot vector in the `u` direction
+    knots_u: Vec<F>,
+    /// The knot vector in the `v` direction
+    knots_v: Vec<F>,
+    /// The knot vector in the `w` direction
+    knots_w: Vec<F>,
+}
+
+impl<T: Interpolate<F> + Copy, F: Float> BSplineVolume<T, F> {
+    /// Create a new B-spline volume of degree `degree_u`, `degree_v` and `degree_w` in the
+    /// `u`, `v` and `w` directions that will interpolate the lattice of `control_points`
+    /// using the knots in each direction. `control_points[i][j][k]` is the control point
+    /// weighted by the `i`-th basis function in `u`, the `j`-th in `v` and the `k`-th in `w`.
+    /// As with `BSpline::new` the knots are sorted for you.
+    ///
+    /// Each direction must have a valid number of control points and knots as described in
+    /// `BSpline::new`, and the lattice must be a box with all slabs and rows the same size,
+    /// or the function will panic. Use `try_new` to validate volumes from untrusted input
+    /// without panicking.
+    pub fn new(
+        degree_u: usize,
+        degree_v: usize,
+        degree_w: usize,
+        control_points: Vec<Vec<Vec<T>>>,
+        mut knots_u: Vec<F>,
+        mut knots_v: Vec<F>,
+        mut knots_w: Vec<F>,
+    ) -> BSplineVolume<T, F> {
+        let (slab_len, row_len) = lattice_shape(&control_points);
+        if control_points.len() <= degree_u || slab_len <= degree_v || row_len <= degree_w {
+            panic!("Too few control points for volume");
+        }
+        if check_lattice(&control_points).is_err() {
+            panic!("Invalid control point lattice, all slabs and rows must be the same size");
+        }
+        let knot_counts = [
+            (knots_u.len(), control_points.len() + degree_u + 1),
+            (knots_v.len(), slab_len + degree_v + 1),
+            (knots_w.len(), row_len + degree_w + 1),
+        ];
+        for (found, expected) in knot_counts {
+            if found != expected {
+                panic!(
+                    "Invalid number of knots, got {}, expected {}",
+                    found, expected
+                );
+            }
+        }
+        knots_u.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        knots_v.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        knots_w.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        BSplineVolume {
+            degree_u,
+            degree_v,
+            degree_w,
+            control_points: control_points.concat().concat(),
+            slab_len,
+            row_len,
+            knots_u,
+            knots_v,
+            knots_w,
+        }
+    }
+    /// Create a new B-spline volume, returning an error instead of panicking if the
+    /// parameters don't describe a valid volume. The knots in each direction are validated
+    /// as in `BSpline::try_new`, every slab of the lattice must have the same number of rows
+    /// and every row the same number of control points.
+    pub fn try_new(
+        degree_u: usize,
+        degree_v: usize,
+        degree_w: usize,
+        control_points: Vec<Vec<Vec<T>>>,
+        knots_u: Vec<F>,
+        knots_v: Vec<F>,
+        knots_w: Vec<F>,
+    ) -> Result<BSplineVolume<T, F>, BSplineError> {
+        check_lattice(&control_points)?;
+        let (slab_len, row_len) = lattice_shape(&control_points);
+        check_knots(degree_u, control_points.len(), &knots_u)?;
+        check_knots(degree_v, slab_len, &knots_v)?;
+        check_knots(degree_w, row_len, &knots_w)?;
+        Ok(BSplineVolume {
+            degree_u,
+            degree_v,
+            degree_w,
+            control_points: control_points.concat().concat(),
+            slab_len,
+            row_len,
+            knots_u,
+            knots_v,
+            knots_w,
+        })
+    }
+    /// Compute a point in the volume at `(u, v, w)`, the parameters **must** be in the
+    /// inclusive ranges of values returned by `knot_domain`. If they are out of bounds this
+    /// function will assert on debug builds.
+    ///
+    /// Volumes with degrees less than 16 are evaluated entirely on the stack, so this never
+    /// allocates.
+    pub fn point(&self, u: F, v: F, w: F) -> T {
+        let ((min_u, max_u), (min_v, max_v), (min_w, max_w)) = self.knot_domain();
+        debug_assert!(u >= min_u && u <= max_u);
+        debug_assert!(v >= min_v && v <= max_v);
+        debug_assert!(w >= min_w && w <= max_w);
+        let (pu, pv, pw) = (self.degree_u, self.degree_v, self.degree_w);
+        let span_u = knot_span(&self.knots_u, pu, u);
+        let span_v = knot_span(&self.knots_v, pv, v);
+        let span_w = knot_span(&self.knots_w, pw, w);
+        with_scratch(&self.control_points[..=pu], |slabs| {
+            for (a, s) in slabs.iter_mut().enumerate() {
+                let i = span_u - pu - 1 + a;
+                *s = with_scratch(&self.control_points[..=pv], |rows| {
+                    for (b, r) in rows.iter_mut().enumerate() {
+                        let row = self.row(i, span_v - pv - 1 + b);
+                        *r = with_scratch(&row[span_w - pw - 1..span_w], |tmp| {
+                            de_boor(tmp, &self.knots_w, pw, w, span_w)
+                        });
+                    }
+                    de_boor(rows, &self.knots_v, pv, v, span_v)
+                });
+            }
+            de_boor(slabs, &self.knots_u, pu, u, span_u)
+        })
+    }
+    /// Get the degree of the volume in the `u` direction.
+    pub fn degree_u(&self) -> usize {
+        self.degree_u
+    }
+    /// Get the degree of the volume in the `v` direction.
+    pub fn degree_v(&self) -> usize {
+        self.degree_v
+    }
+    /// Get the degree of the volume in the `w` direction.
+    pub fn degree_w(&self) -> usize {
+        self.degree_w
+    }
+    /// Get the number of control points in the `u`, `v` and `w` directions, the number of
+    /// slabs in the lattice, rows in each slab and control points in each row.
+    pub fn control_point_count(&self) -> (usize, usize, usize) {
+        let slab_size = self.slab_len * self.row_len;
+        (
+            self.control_points.len() / slab_size,
+            self.slab_len,
+            self.row_len,
+        )
+    }
+    /// Get the control point at index `k` of row `j` of slab `i` of the lattice.
+    pub fn control_point(&self, i: usize, j: usize, k: usize) -> &T {
+        &self.row(i, j)[k]
+    }
+    /// Get a mutable reference to the control point at index `k` of row `j` of slab `i` of
+    /// the lattice, for moving the control points of a deformation lattice.
+    pub fn control_point_mut(&mut self, i: usize, j: usize, k: usize) -> &mut T {
+        let start = (i * self.slab_len + j) * self.row_len;
+        &mut self.control_points[start..start + self.row_len][k]
+    }
+    /// Get an iterator over the control points, going through the lattice slab by slab and
+    /// row by row.
+    pub fn control_points(&self) -> Iter<'_, T> {
+        self.control_points.iter()
+    }
+    /// Get an iterator over the knots in the `u` direction.
+    pub fn knots_u(&self) -> Iter<'_, F> {
+        self.knots_u.iter()
+    }
+    /// Get an iterator over the knots in the `v` direction.
+    pub fn knots_v(&self) -> Iter<'_, F> {
+        self.knots_v.iter()
+    }
+    /// Get an iterator over the knots in the `w` direction.
+    pub fn knots_w(&self) -> Iter<'_, F> {
+        self.knots_w.iter()
+    }
+    /// Get the parameter box the volume is defined over, the min and max knot domain values
+    /// in the `u`, `v` and `w` directions.
+    pub fn knot_domain(&self) -> ((F, F), (F, F), (F, F)) {
+        (
+            domain(&self.knots_u, self.degree_u),
+            domain(&self.knots_v, self.degree_v),
+            domain(&self.knots_w, self.degree_w),
+        )
+    }
+    /// Get row `j` of slab `i` of the control point lattice
+    fn row(&self, i: usize, j: usize) -> &[T] {
+        let start = (i * self.slab_len + j) * self.row_len;
+        &self.control_points[start..start + self.row_len]
+    }
+}
+
+/// Get the knot domain of a curve of `degree` with the `knots`
+fn domain<F: Float>(knots: &[F], degree: usize) -> (F, F) {
+    (knots[degree], knots[knots.len() - 1 - degree])
+}
+
+/// Get the number of rows in each slab and control points in each row of a lattice, taken
+/// from its first slab and row.
+fn lattice_shape<T>(control_points: &[Vec<Vec<T>>]) -> (usize, usize) {
+    let slab_len = control_points.first().map_or(0, |s| s.len());
+    let row_len = control_points
+        .first()
+        .and_then(|s| s.first())
+        .map_or(0, |r| r.len());
+    (slab_len, row_len)
+}
+
+/// Check that every slab of the lattice has the same number of rows and every row the same
+/// number of control points.
+fn check_lattice<T>(control_points: &[Vec<Vec<T>>]) -> Result<(), BSplineError> {
+    let (slab_len, row_len) = lattice_shape(control_points);
+    if let Some(slab) = control_points.iter().position(|s| s.len() != slab_len) {
+        return Err(BSplineError::InvalidLatticeSlab {
+            slab,
+            expected: slab_len,
+            found: control_points[slab].len(),
+        });
+    }
+    match control_points
+        .iter()
+        .flatten()
+        .enumerate()
+        .find(|(_, r)| r.len() != row_len)
+    {
+        Some((row, r)) => Err(BSplineError::InvalidGridRow {
+            row,
+            expected: row_len,
+            found: r.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// A free-form deformation, which deforms space by embedding it in a box shaped lattice of
+/// control points and moving the control points. Points inside the box are mapped to their
+/// parameters in the box and through a `BSplineVolume` defined by the lattice.
+///
+/// The lattice starts out with its control points spread through the box so the volume maps
+/// each point in the box to itself, and `control_point_mut` moves the control points to
+/// deform the space. Finding the parameters of points in the box needs the cross product.
+#[derive(Clone, Debug)]
+pub struct FreeFormDeformation<T: Cross<F>, F: Float> {
+    /// The deformed lattice, with a knot domain of `[0, 1]` in each direction
+    volume: BSplineVolume<T, F>,
+    /// The corner of the box at the parameters `(0, 0, 0)`
+    origin: T,
+    /// The edges of the box along the `u`, `v` and `w` directions
+    axes: [T; 3],
+}
+
+impl<T: Cross<F>, F: Float> FreeFormDeformation<T, F> {
+    /// Create an undeformed lattice in the box with a corner at `origin` and edges along the
+    /// `axes`, which must be linearly independent. The lattice has `counts` control points in
+    /// each direction and uses clamped uniform knots of the `degrees` in each direction.
+    ///
+    /// The control points are placed at the Greville abscissae of the knots, the averages of
+    /// each control point's `degree` interior knots, which is what makes the undeformed
+    /// lattice map the box to itself. The degrees must be at least 1 and there must be more
+    /// control points than the degree in each direction, otherwise the function will panic.
+    pub fn new(
+        origin: T,
+        axes: [T; 3],
+        degrees: [usize; 3],
+        counts: [usize; 3],
+    ) -> FreeFormDeformation<T, F> {
+        if degrees.contains(&0) {
+            panic!("The degree of a deformation lattice must be at least 1");
+        }
+        let knots: Vec<Vec<F>> = (0..3)
+            .map(|d| {
+                KnotVector::clamped(degrees[d], counts[d])
+                    .rescale(F::zero(), F::one())
+                    .into()
+            })
+            .collect();
+        let greville: Vec<Vec<F>> = (0..3)
+            .map(|d| {
+                let p = degrees[d];
+                (0..counts[d])
+                    .map(|i| {
+                        let sum = knots[d][i + 1..=i + p]
+                            .iter()
+                            .fold(F::zero(), |s, &k| s + k);
+                        sum / from_usize(p)
+                    })
+                    .collect()
+            })
+            .collect();
+        let control_points = greville[0]
+            .iter()
+            .map(|&a| {
+                greville[1]
+                    .iter()
+                    .map(|&b| {
+                        greville[2]
+                            .iter()
+                            .map(|&c| {
+                                origin
+                                    .sum(&axes[0].scale(a))
+                                    .sum(&axes[1].scale(b))
+                                    .sum(&axes[2].scale(c))
+                            })
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut knots = knots.into_iter();
+        let volume = BSplineVolume::new(
+            degrees[0],
+            degrees[1],
+            degrees[2],
+            control_points,
+            knots.next().unwrap(),
+            knots.next().unwrap(),
+            knots.next().unwrap(),
+        );
+        FreeFormDeformation {
+            volume,
+            origin,
+            axes,
+        }
+    }
+    /// Deform `point` through the lattice. Points inside the box are mapped to their
+    /// parameters in the box and evaluated on the lattice's volume, points outside the box
+    /// are returned unchanged. Keeping the control points on the faces of the lattice in
+    /// place keeps the deformation continuous across the faces of the box.
+    pub fn deform(&self, point: T) -> T {
+        let (u, v, w) = self.parameters(point);
+        let inside = |x: F| x >= F::zero() && x <= F::one();
+        if inside(u) && inside(v) && inside(w) {
+            self.volume.point(u, v, w)
+        } else {
+            point
+        }
+    }
+    /// Find the parameters `(u, v, w)` of `point` in the undeformed box, the coordinates of
+    /// the point along the box's axes. Points inside the box have parameters in `[0, 1]`.
+    pub fn parameters(&self, point: T) -> (F, F, F) {
+        let d = point.difference(&self.origin);
+        let [a, b, c] = &self.axes;
+        let coordinate = |n: T, axis: &T| n.dot(&d) / n.dot(axis);
+        (
+            coordinate(b.cross(c), a),
+            coordinate(c.cross(a), b),
+            coordinate(a.cross(b), c),
+        )
+    }
+    /// Get the control point at index `k` of row `j` of slab `i` of the lattice.
+    pub fn control_point(&self, i: usize, j: usize, k: usize) -> &T {
+        self.volume.control_point(i, j, k)
+    }
+    /// Get a mutable reference to the control point at index `k` of row `j` of slab `i` of
+    /// the lattice, moving the control points deforms the space around them.
+    pub fn control_point_mut(&mut self, i: usize, j: usize, k: usize) -> &mut T {
+        self.volume.control_point_mut(i, j, k)
+    }
+    /// Get the volume defined by the lattice, which maps the parameters of points in the box
+    /// to the deformed points.
+    pub fn volume(&self) -> &BSplineVolume<T, F> {
+        &self.volume
+    }
+}
@@ -0,0 +1,86 @@
+extern crate bspline;
+use bspline::{BSpline, KnotVector};
+
+fn cubic() -> BSpline<f64, f64> {
+    let points = vec![0.0, 2.0, -1.0, 3.0, 1.0, 0.5, 2.0];
+    let knots = vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.5, 1.5, 3.0, 3.0, 3.0, 3.0];
+    BSpline::new(3, points, knots)
+}
+
+#[test]
+fn find_span() {
+    let spline = cubic();
+    assert_eq!(spline.find_span(0.0), 3);
+    assert_eq!(spline.find_span(0.25), 3);
+    assert_eq!(spline.find_span(0.5), 4);
+    assert_eq!(spline.find_span(1.5), 6);
+    assert_eq!(spline.find_span(2.0), 6);
+    // The end of the domain is in the last non-empty span
+    assert_eq!(spline.find_span(3.0), 6);
+}
+
+#[test]
+fn partition_of_unity() {
+    let spline = cubic();
+    for i in 0..=30 {
+        let t = 3.0 * i as f64 / 30.0;
+        let (span, basis) = spline.basis_functions(t);
+        assert_eq!(span, spline.find_span(t));
+        assert_eq!(basis.len(), 4);
+        assert!(basis.iter().all(|&b| b >= 0.0));
+        let sum: f64 = basis.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn weighted_control_points() {
+    let spline = cubic();
+    let points: Vec<f64> = spline.control_points().cloned().collect();
+    for i in 0..=30 {
+        let t = 3.0 * i as f64 / 30.0;
+        let (span, basis) = spline.basis_functions(t);
+        let p: f64 = basis
+            .iter()
+            .enumerate()
+            .map(|(r, b)| b * points[span - 3 + r])
+            .sum();
+        assert!((p - spline.point(t)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn derivatives() {
+    let spline = cubic();
+    let points: Vec<f64> = spline.control_points().cloned().collect();
+    for i in 0..=30 {
+        let t = 3.0 * i as f64 / 30.0;
+        let (span, derivs) = spline.basis_derivatives(t, 4);
+        assert_eq!(derivs.len(), 5);
+        for (k, d) in derivs.iter().enumerate() {
+            let value: f64 = d
+                .iter()
+                .enumerate()
+                .map(|(r, b)| b * points[span - 3 + r])
+                .sum();
+            assert!((value - spline.derivative(t, k)).abs() < 1e-9);
+        }
+        // The derivatives of the basis functions sum to zero
+        for d in &derivs[1..] {
+            assert!(d.iter().sum::<f64>().abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn knot_vector() {
+    let k = KnotVector::<f64>::clamped(2, 5);
+    assert_eq!(k.find_span(0.0), 2);
+    assert_eq!(k.find_span(3.0), 4);
+    let (span, basis) = k.basis_functions(1.5);
+    assert_eq!(span, 3);
+    assert_eq!(basis, vec![0.125, 0.75, 0.125]);
+    let (span, derivs) = k.basis_derivatives(1.5, 1);
+    assert_eq!(span, 3);
+    assert_eq!(derivs[1], vec![-0.5, 0.0, 0.5]);
+}
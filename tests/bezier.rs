@@ -0,0 +1,52 @@
+extern crate bspline;
+use bspline::BSpline;
+
+fn check_segments(spline: &BSpline<f64, f64>, expect_segments: usize) {
+    let segments = spline.to_bezier_segments();
+    assert_eq!(segments.len(), expect_segments);
+    let (min, max) = spline.knot_domain();
+    assert_eq!(segments[0].interval().0, min);
+    assert_eq!(segments[segments.len() - 1].interval().1, max);
+    for (i, segment) in segments.iter().enumerate() {
+        assert_eq!(segment.degree(), spline.degree());
+        assert_eq!(segment.control_points().len(), spline.degree() + 1);
+        let (start, end) = segment.interval();
+        if i > 0 {
+            assert_eq!(segments[i - 1].interval().1, start);
+        }
+        // A Bézier curve interpolates its end points
+        let cps = segment.control_points();
+        assert!((cps[0] - spline.point(start)).abs() < 1e-12);
+        assert!((cps[cps.len() - 1] - spline.point(end)).abs() < 1e-12);
+        for j in 0..=20 {
+            let t = start + (end - start) * j as f64 / 20.0;
+            assert!((segment.point(t) - spline.point(t)).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn cubic_bezier_segments() {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    check_segments(&BSpline::new(3, points, knots), 4);
+}
+
+#[test]
+fn quadratic_bezier_segments() {
+    // A repeated interior knot already at full continuity
+    let points = vec![0.0, 0.5, 1.0, 0.0, 0.5, -1.0];
+    let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 3.0, 3.0, 3.0];
+    check_segments(&BSpline::new(2, points, knots), 3);
+}
+
+#[test]
+fn unclamped_bezier_segments() {
+    let points = vec![-1.5, 1.5, -1.5, 1.5];
+    let knots = vec![0.0, 1.0, 2.0, 2.0, 5.0, 5.0, 6.0, 7.0];
+    check_segments(&BSpline::new(3, points, knots), 1);
+
+    let points = vec![0.0, 1.0, 2.0, 3.0, 2.0];
+    let knots = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+    check_segments(&BSpline::new(2, points, knots), 3);
+}
@@ -0,0 +1,93 @@
+//! Point types and checks shared by the integration tests.
+#![allow(dead_code)]
+
+use bspline::{BSpline, Cross, InnerProduct};
+use std::ops::{Add, Mul};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+impl Point {
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+    pub fn distance(&self, other: &Point) -> f64 {
+        (*self + *other * -1.0).norm()
+    }
+}
+impl Mul<f64> for Point {
+    type Output = Point;
+    fn mul(self, rhs: f64) -> Point {
+        Point {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+impl Add for Point {
+    type Output = Point;
+    fn add(self, rhs: Point) -> Point {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+impl InnerProduct<f64> for Point {
+    fn dot(&self, other: &Point) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Point3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+impl Point3 {
+    pub fn new(x: f64, y: f64, z: f64) -> Point3 {
+        Point3 { x, y, z }
+    }
+    pub fn distance(&self, other: &Point3) -> f64 {
+        (*self + *other * -1.0).norm()
+    }
+}
+impl Mul<f64> for Point3 {
+    type Output = Point3;
+    fn mul(self, rhs: f64) -> Point3 {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+impl Add for Point3 {
+    type Output = Point3;
+    fn add(self, rhs: Point3) -> Point3 {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+impl InnerProduct<f64> for Point3 {
+    fn dot(&self, other: &Point3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+impl Cross<f64> for Point3 {
+    fn cross(&self, other: &Point3) -> Point3 {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+}
+
+/// Check the curves trace the same shape over the domain of `a`, within `tolerance`
+pub fn check_same_curve(a: &BSpline<f64, f64>, b: &BSpline<f64, f64>, tolerance: f64) {
+    assert_eq!(a.knot_domain(), b.knot_domain());
+    let (min, max) = a.knot_domain();
+    for i in 0..=200 {
+        let t = min + (max - min) * i as f64 / 200.0;
+        assert!((a.point(t) - b.point(t)).abs() <= tolerance);
+    }
+}
@@ -0,0 +1,142 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, BSplineError};
+use common::check_same_curve;
+
+fn test_curves() -> Vec<BSpline<f64, f64>> {
+    vec![
+        BSpline::new(0, vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 2.5, 3.0]),
+        BSpline::new(
+            1,
+            vec![0.0, 1.0, -1.0, 0.5],
+            vec![0.0, 0.0, 1.0, 2.5, 3.0, 3.0],
+        ),
+        BSpline::new(
+            2,
+            vec![0.0, 0.0, 1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 3.0],
+        ),
+        BSpline::new(
+            3,
+            vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0],
+            vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0],
+        ),
+        // Repeated interior knots of varying multiplicity
+        BSpline::new(
+            3,
+            vec![0.0, 2.0, -1.0, 3.0, 1.0, 0.5, -2.0, 1.0, 0.0, 1.5],
+            vec![
+                0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 3.0, 3.0, 3.0, 4.0, 4.0, 4.0, 4.0,
+            ],
+        ),
+        // Interior knots repeated degree + 1 times, where the curves are discontinuous
+        BSpline::new(
+            1,
+            vec![0.0, 1.0, 2.0, 3.0],
+            vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0],
+        ),
+        BSpline::new(
+            2,
+            vec![0.0, 2.0, -1.0, 3.0, 1.0, 0.5, -2.0],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 3.0, 3.0, 3.0],
+        ),
+        BSpline::new(
+            4,
+            vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+            vec![
+                0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 5.0, 5.0,
+            ],
+        ),
+    ]
+}
+
+#[test]
+fn elevate_degree() {
+    for spline in test_curves().iter() {
+        for times in 0..4 {
+            let elevated = spline.elevate_degree(times);
+            assert_eq!(elevated.degree(), spline.degree() + times);
+            check_same_curve(spline, &elevated, 1e-10);
+            // Each distinct knot gains `times` extra repetitions
+            let knots: Vec<f64> = spline.knots().cloned().collect();
+            let mut distinct = knots.clone();
+            distinct.dedup();
+            for k in distinct.iter() {
+                let before = knots.iter().filter(|&x| x == k).count();
+                let after = elevated.knots().filter(|&x| x == k).count();
+                assert_eq!(after, before + times);
+            }
+        }
+    }
+}
+
+#[test]
+fn elevate_unclamped() {
+    let spline = BSpline::new(
+        3,
+        vec![-1.5, 1.5, -1.5, 1.5, 0.0],
+        vec![0.0, 1.0, 2.0, 2.0, 4.0, 5.0, 5.0, 6.0, 7.0],
+    );
+    let elevated = spline.elevate_degree(2);
+    assert_eq!(elevated.degree(), 5);
+    check_same_curve(&spline, &elevated, 1e-10);
+}
+
+#[test]
+fn reduce_elevated() {
+    for curve in test_curves().iter().filter(|c| c.degree() > 0) {
+        let elevated = curve.elevate_degree(1);
+        let (reduced, deviation) = elevated.reduce_degree(1e-10).unwrap();
+        assert_eq!(reduced.degree(), curve.degree());
+        assert!(deviation < 1e-10);
+        // The knots added by elevating the degree are removed again
+        assert!(reduced.knots().eq(curve.knots()));
+        check_same_curve(curve, &reduced, 1e-10);
+    }
+}
+
+#[test]
+fn reduce_quartic() {
+    // The quartic curve from the plot1d example
+    let points = vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let knots = vec![
+        0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 5.0, 5.0,
+    ];
+    let quartic = BSpline::new(4, points, knots);
+    assert_eq!(
+        quartic.reduce_degree(1e-3).unwrap_err(),
+        BSplineError::ToleranceExceeded
+    );
+
+    let (cubic, deviation) = quartic.reduce_degree(0.1).unwrap();
+    assert_eq!(cubic.degree(), 3);
+    assert_eq!(cubic.knot_domain(), quartic.knot_domain());
+    assert!(deviation > 0.0 && deviation <= 0.1);
+    for i in 0..=500 {
+        let t = i as f64 / 100.0;
+        assert!((cubic.point(t) - quartic.point(t)).abs() <= deviation);
+    }
+
+    // With enough tolerance the cubic keeps the single interior knots of the quartic and is
+    // C2 continuous at them
+    let (cubic, deviation) = quartic.reduce_degree(0.5).unwrap();
+    let knots: Vec<f64> = cubic.knots().cloned().collect();
+    assert_eq!(
+        knots,
+        vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 5.0]
+    );
+    assert_eq!(cubic.control_points().count(), 8);
+    for i in 0..=500 {
+        let t = i as f64 / 100.0;
+        assert!((cubic.point(t) - quartic.point(t)).abs() <= deviation);
+    }
+}
+
+#[test]
+fn reduce_degree_too_low() {
+    let line = BSpline::new(1, vec![0.0, 1.0], vec![0.0, 0.0, 1.0, 1.0]);
+    assert_eq!(
+        line.reduce_degree(1.0).unwrap_err(),
+        BSplineError::DegreeTooLow { degree: 1, min: 2 }
+    );
+}
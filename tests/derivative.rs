@@ -0,0 +1,59 @@
+extern crate bspline;
+use bspline::BSpline;
+
+fn quadratic_spline() -> BSpline<f64, f64> {
+    let points = vec![0.0, 0.0, 1.0, 0.0, 0.0];
+    let knots = vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 3.0];
+    BSpline::new(2, points, knots)
+}
+
+fn cubic_spline() -> BSpline<f64, f64> {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    BSpline::new(3, points, knots)
+}
+
+#[test]
+fn quadratic_derivatives() {
+    // On [0, 1] the curve is t^2 / 2 and on [1, 2] it is 3/4 - (t - 3/2)^2
+    let spline = quadratic_spline();
+    let expect: Vec<(f64, f64, f64)> = vec![
+        (0.0, 0.0, 1.0),
+        (0.5, 0.5, 1.0),
+        (1.2, 0.6, -2.0),
+        (1.5, 0.0, -2.0),
+        (1.8, -0.6, -2.0),
+    ];
+    for &(t, d1, d2) in expect.iter() {
+        assert!((spline.derivative(t, 1) - d1).abs() < 1e-12);
+        assert!((spline.derivative(t, 2) - d2).abs() < 1e-12);
+        assert_eq!(spline.derivative(t, 3), 0.0);
+        assert_eq!(spline.derivative(t, 0), spline.point(t));
+    }
+}
+
+#[test]
+fn cubic_derivative_finite_difference() {
+    let spline = cubic_spline();
+    let h = 1e-6;
+    for i in 1..40 {
+        let t = -2.0 + 0.1 * i as f64;
+        let fd = (spline.point(t + h) - spline.point(t - h)) / (2.0 * h);
+        assert!((spline.derivative(t, 1) - fd).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn derivative_curve_matches_derivative() {
+    let spline = cubic_spline();
+    let first = spline.derivative_curve();
+    let second = first.derivative_curve();
+    assert_eq!(first.degree(), 2);
+    assert_eq!(second.degree(), 1);
+    assert_eq!(first.knot_domain(), spline.knot_domain());
+    for i in 0..=40 {
+        let t = -2.0 + 0.1 * i as f64;
+        assert!((first.point(t) - spline.derivative(t, 1)).abs() < 1e-12);
+        assert!((second.point(t) - spline.derivative(t, 2)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,51 @@
+extern crate bspline;
+use bspline::BSpline;
+
+fn quadratic_spline() -> BSpline<f64, f64> {
+    let points = vec![0.0, 0.0, 1.0, 0.0, 0.0];
+    let knots = vec![0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 3.0, 3.0];
+    BSpline::new(2, points, knots)
+}
+
+#[test]
+fn try_point() {
+    let spline = quadratic_spline();
+    assert_eq!(spline.try_point(1.5), Some(0.75));
+    assert_eq!(spline.try_point(3.0), Some(0.0));
+    assert_eq!(spline.try_point(-0.1), None);
+    assert_eq!(spline.try_point(3.0 + 1e-12), None);
+    assert_eq!(spline.try_point(f64::NAN), None);
+}
+
+#[test]
+fn point_clamped() {
+    let spline = quadratic_spline();
+    assert_eq!(spline.point_clamped(1.5), 0.75);
+    assert_eq!(spline.point_clamped(-10.0), spline.point(0.0));
+    assert_eq!(spline.point_clamped(3.0 + 1e-12), spline.point(3.0));
+    assert_eq!(spline.point_clamped(f64::NEG_INFINITY), spline.point(0.0));
+    assert_eq!(spline.point_clamped(f64::INFINITY), spline.point(3.0));
+    assert!(spline.point_clamped(f64::NAN).is_nan());
+}
+
+#[test]
+fn point_extrapolated() {
+    // On [0, 1] the curve is t^2 / 2 and on [2, 3] it is (3 - t)^2 / 2
+    let spline = quadratic_spline();
+    assert_eq!(spline.point_extrapolated(1.5), 0.75);
+    assert!((spline.point_extrapolated(-1.0) - 0.5).abs() < 1e-12);
+    assert!((spline.point_extrapolated(5.0) - 2.0).abs() < 1e-12);
+    assert!(spline.point_extrapolated(f64::NAN).is_nan());
+    assert!(!spline.point_extrapolated(f64::INFINITY).is_finite());
+    assert!(!spline.point_extrapolated(f64::NEG_INFINITY).is_finite());
+
+    // The domain of this curve starts on a repeated knot, so the span just before it is
+    // empty and the first non-empty span must be extended instead
+    let points: Vec<f64> = vec![0.0, 1.0, 2.0];
+    let knots = vec![0.0, 1.0, 1.0, 2.0, 2.0];
+    let spline = BSpline::new(1, points, knots);
+    assert_eq!(spline.knot_domain(), (1.0, 2.0));
+    assert_eq!(spline.point(2.0), 2.0);
+    assert!((spline.point_extrapolated(0.5) - 0.5).abs() < 1e-12);
+    assert!((spline.point_extrapolated(2.5) - 2.5).abs() < 1e-12);
+}
@@ -0,0 +1,239 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, BSplineError, FitOptions, InnerProduct, Parameterization};
+use common::Point;
+
+fn data() -> Vec<Point> {
+    vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 2.0),
+        Point::new(1.5, 2.1),
+        Point::new(4.0, 0.5),
+        Point::new(5.0, -1.0),
+        Point::new(7.0, 0.0),
+        Point::new(7.2, 3.0),
+    ]
+}
+
+/// Compute the parameters of the data points the same way the fit should
+fn params(points: &[Point], parameterization: Parameterization) -> Vec<f64> {
+    let n = points.len() - 1;
+    let d: Vec<f64> = (1..=n)
+        .map(|k| {
+            let p = points[k] + points[k - 1] * -1.0;
+            match parameterization {
+                Parameterization::Uniform => 1.0,
+                Parameterization::ChordLength => p.norm(),
+                Parameterization::Centripetal => p.norm().sqrt(),
+            }
+        })
+        .collect();
+    let total: f64 = d.iter().sum();
+    let mut u = vec![0.0];
+    for x in d {
+        u.push(u[u.len() - 1] + x / total);
+    }
+    u
+}
+
+#[test]
+fn interpolate_points() {
+    let points = data();
+    for &parameterization in &[
+        Parameterization::Uniform,
+        Parameterization::ChordLength,
+        Parameterization::Centripetal,
+    ] {
+        let params = params(&points, parameterization);
+        for degree in 1..=4 {
+            let curve = BSpline::interpolate_points(degree, &points, parameterization).unwrap();
+            assert_eq!(curve.degree(), degree);
+            assert_eq!(curve.knot_domain(), (0.0, 1.0));
+            assert_eq!(curve.control_points().len(), points.len());
+            for (p, &u) in points.iter().zip(params.iter()) {
+                let c = curve.point(u);
+                assert!((c.x - p.x).abs() < 1e-10 && (c.y - p.y).abs() < 1e-10);
+            }
+        }
+    }
+}
+
+#[test]
+fn interpolate_line() {
+    // Interpolating points on a line gives back the line, evenly parameterized by length
+    let points: Vec<f64> = vec![0.0, 0.5, 2.0, 2.5, 4.0];
+    let curve = BSpline::interpolate_points(3, &points, Parameterization::ChordLength).unwrap();
+    for i in 0..=100 {
+        let t = i as f64 / 100.0;
+        assert!((curve.point(t) - 4.0 * t).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn interpolate_points_errors() {
+    let points = data();
+    assert_eq!(
+        BSpline::interpolate_points(0, &points, Parameterization::Uniform).unwrap_err(),
+        BSplineError::DegreeTooLow { degree: 0, min: 1 }
+    );
+    assert_eq!(
+        BSpline::interpolate_points(3, &points[..3], Parameterization::Uniform).unwrap_err(),
+        BSplineError::TooFewControlPoints {
+            degree: 3,
+            found: 3
+        }
+    );
+    let repeated = vec![0.0, 1.0, 1.0, 2.0];
+    assert_eq!(
+        BSpline::interpolate_points(2, &repeated, Parameterization::ChordLength).unwrap_err(),
+        BSplineError::SingularSystem
+    );
+}
+
+fn noisy_samples() -> Vec<(f64, f64)> {
+    (0..=60)
+        .map(|i| {
+            let t = i as f64 / 20.0;
+            (t, t.sin() + 0.05 * (37.0 * t).sin())
+        })
+        .collect()
+}
+
+fn squared_error(curve: &BSpline<f64, f64>, samples: &[(f64, f64)]) -> f64 {
+    samples
+        .iter()
+        .map(|&(t, y)| (curve.point(t) - y).powi(2))
+        .sum()
+}
+
+#[test]
+fn least_squares_polynomial() {
+    // Samples of a cubic polynomial are fit exactly by any cubic spline
+    let f = |t: f64| 1.0 - 2.0 * t + 0.5 * t * t - 0.25 * t * t * t;
+    let samples: Vec<(f64, f64)> = (0..=20)
+        .map(|i| {
+            let t = -1.0 + 3.0 * (i as f64 / 20.0).powi(2);
+            (t, f(t))
+        })
+        .collect();
+    let curve = BSpline::fit_least_squares(3, 8, &samples).unwrap();
+    assert_eq!(curve.knot_domain(), (-1.0, 2.0));
+    assert_eq!(curve.control_points().len(), 8);
+    for i in 0..=100 {
+        let t = -1.0 + 3.0 * i as f64 / 100.0;
+        assert!((curve.point(t) - f(t)).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn least_squares_minimizes_error() {
+    let samples = noisy_samples();
+    let curve = BSpline::fit_least_squares(3, 8, &samples).unwrap();
+    for i in 0..=60 {
+        let t = i as f64 / 20.0;
+        assert!((curve.point(t) - t.sin()).abs() < 0.05);
+    }
+    // Moving any of the control points increases the error
+    let error = squared_error(&curve, &samples);
+    let knots: Vec<f64> = curve.knots().cloned().collect();
+    for i in 0..8 {
+        for &delta in &[-1e-3, 1e-3] {
+            let mut points: Vec<f64> = curve.control_points().cloned().collect();
+            points[i] += delta;
+            let moved = BSpline::new(3, points, knots.clone());
+            assert!(squared_error(&moved, &samples) > error);
+        }
+    }
+}
+
+#[test]
+fn least_squares_options() {
+    let samples = noisy_samples();
+    let fixed = FitOptions::new().with_fixed_endpoints(true);
+    let curve = BSpline::fit_least_squares_with(3, 6, &samples, &fixed).unwrap();
+    assert_eq!(curve.point(0.0), samples[0].1);
+    assert_eq!(curve.point(3.0), samples[60].1);
+
+    // A heavily weighted sample pulls the curve onto it
+    let mut weights = vec![1.0; samples.len()];
+    weights[30] = 1e8;
+    let weighted = FitOptions::new().with_weights(weights);
+    let curve = BSpline::fit_least_squares_with(3, 6, &samples, &weighted).unwrap();
+    assert!((curve.point(samples[30].0) - samples[30].1).abs() < 1e-6);
+
+    // The samples don't need to be sorted
+    let mut reversed = samples.clone();
+    reversed.reverse();
+    let a = BSpline::fit_least_squares(2, 5, &samples).unwrap();
+    let b = BSpline::fit_least_squares(2, 5, &reversed).unwrap();
+    for i in 0..=30 {
+        let t = i as f64 / 10.0;
+        assert!((a.point(t) - b.point(t)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn least_squares_points() {
+    // Fit a noisy circle of 2D points, closing the curve by fixing its ends
+    let samples: Vec<(f64, Point)> = (0..=50)
+        .map(|i| {
+            let t = i as f64 / 50.0;
+            let a = 2.0 * std::f64::consts::PI * t;
+            let r = 1.0 + 0.01 * (23.0 * a).sin();
+            (t, Point::new(r * a.cos(), r * a.sin()))
+        })
+        .collect();
+    let options = FitOptions::new().with_fixed_endpoints(true);
+    let curve = BSpline::fit_least_squares_with(3, 12, &samples, &options).unwrap();
+    let start = curve.point(0.0);
+    let end = curve.point(1.0);
+    assert!((start.x - end.x).abs() < 1e-12 && (start.y - end.y).abs() < 1e-12);
+    for i in 0..=100 {
+        let p = curve.point(i as f64 / 100.0);
+        assert!((p.norm() - 1.0).abs() < 0.02);
+    }
+}
+
+#[test]
+fn least_squares_errors() {
+    let samples = noisy_samples();
+    assert_eq!(
+        BSpline::fit_least_squares(3, 3, &samples).unwrap_err(),
+        BSplineError::TooFewControlPoints {
+            degree: 3,
+            found: 3
+        }
+    );
+    assert_eq!(
+        BSpline::fit_least_squares(3, 62, &samples).unwrap_err(),
+        BSplineError::SingularSystem
+    );
+    let options = FitOptions::new().with_weights(vec![1.0; 3]);
+    assert_eq!(
+        BSpline::fit_least_squares_with(3, 6, &samples, &options).unwrap_err(),
+        BSplineError::InvalidWeightCount {
+            expected: 61,
+            found: 3
+        }
+    );
+    assert_eq!(
+        BSpline::fit_least_squares(1, 2, &[(1.0, 0.0), (1.0, 2.0)]).unwrap_err(),
+        BSplineError::EmptyKnotDomain
+    );
+}
+
+#[test]
+fn least_squares_duplicate_params() {
+    // Repeated parameters are fine as long as every knot span still has samples in it
+    let mut samples = noisy_samples();
+    samples.insert(30, samples[30]);
+    assert!(BSpline::fit_least_squares(3, 8, &samples).is_ok());
+
+    // Too many samples sharing a parameter leave the curve undetermined
+    let mut samples = vec![(0.0, 0.0), (0.5, 1.0), (1.5, 1.0), (2.0, 0.0)];
+    samples.splice(2..2, (0..4).map(|i| (1.0, i as f64)));
+    assert_eq!(
+        BSpline::fit_least_squares(1, 8, &samples).unwrap_err(),
+        BSplineError::SingularSystem
+    );
+}
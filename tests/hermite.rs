@@ -0,0 +1,192 @@
+extern crate bspline;
+use bspline::{BSpline, BSplineError, EndCondition};
+
+fn keys() -> Vec<(f64, f64)> {
+    vec![
+        (0.0, 1.0),
+        (0.5, 2.0),
+        (1.2, -1.0),
+        (2.0, 0.5),
+        (2.4, 3.0),
+        (3.0, 2.0),
+    ]
+}
+
+fn check_keys(curve: &BSpline<f64, f64>, keys: &[(f64, f64)]) {
+    assert_eq!(curve.degree(), 3);
+    assert_eq!(curve.knot_domain(), (keys[0].0, keys[keys.len() - 1].0));
+    for &(u, p) in keys {
+        assert!((curve.point(u) - p).abs() < 1e-10);
+    }
+}
+
+/// Check the `order`-th derivative of the curve is continuous at `u`
+fn check_continuous(curve: &BSpline<f64, f64>, u: f64, order: usize) {
+    let h = 1e-7;
+    let left = curve.derivative(u - h, order);
+    let right = curve.derivative(u + h, order);
+    assert!((left - right).abs() < 1e-4);
+}
+
+#[test]
+fn natural() {
+    let keys = keys();
+    let curve = BSpline::interpolate_hermite(&keys, &[], EndCondition::Natural).unwrap();
+    check_keys(&curve, &keys);
+    assert!(curve.derivative(0.0, 2).abs() < 1e-10);
+    assert!(curve.derivative(3.0, 2).abs() < 1e-10);
+    for &(u, _) in &keys[1..5] {
+        check_continuous(&curve, u, 2);
+    }
+}
+
+#[test]
+fn not_a_knot() {
+    // Not-a-knot end conditions reproduce cubic polynomials
+    let f = |t: f64| 2.0 - t + 0.5 * t * t - 0.3 * t * t * t;
+    let keys: Vec<(f64, f64)> = keys().iter().map(|&(u, _)| (u, f(u))).collect();
+    let curve = BSpline::interpolate_hermite(&keys, &[], EndCondition::NotAKnot).unwrap();
+    check_keys(&curve, &keys);
+    for i in 0..=30 {
+        let t = i as f64 / 10.0;
+        assert!((curve.point(t) - f(t)).abs() < 1e-10);
+    }
+
+    // The second and second to last keys aren't knots, so the third derivative is
+    // continuous there, even with a derivative given at another key
+    let keys = self::keys();
+    let mut derivatives = vec![None; keys.len()];
+    derivatives[2] = Some(-4.0);
+    let curve = BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::NotAKnot).unwrap();
+    check_keys(&curve, &keys);
+    assert!((curve.derivative(1.2, 1) + 4.0).abs() < 1e-10);
+    check_continuous(&curve, 0.5, 3);
+    check_continuous(&curve, 2.4, 3);
+}
+
+#[test]
+fn not_a_knot_derivative() {
+    let keys = keys();
+    let mut derivatives = vec![None; keys.len()];
+    derivatives[1] = Some(1.0);
+    assert_eq!(
+        BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::NotAKnot).unwrap_err(),
+        BSplineError::NotAKnotDerivative { index: 1 }
+    );
+    derivatives[1] = None;
+    derivatives[4] = Some(1.0);
+    assert_eq!(
+        BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::NotAKnot).unwrap_err(),
+        BSplineError::NotAKnotDerivative { index: 4 }
+    );
+    // With a derivative at the end the condition isn't used, so the next key can have one
+    derivatives[5] = Some(0.0);
+    let curve = BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::NotAKnot).unwrap();
+    check_keys(&curve, &keys);
+    assert!((curve.derivative(2.4, 1) - 1.0).abs() < 1e-10);
+    check_continuous(&curve, 0.5, 3);
+}
+
+#[test]
+fn hermite() {
+    let keys = keys();
+    let derivatives = vec![Some(0.0), None, Some(-4.0), Some(2.0), None, Some(1.0)];
+    for &end in &[EndCondition::Natural, EndCondition::NotAKnot] {
+        let curve = BSpline::interpolate_hermite(&keys, &derivatives, end).unwrap();
+        check_keys(&curve, &keys);
+        for (i, (&(u, _), d)) in keys.iter().zip(derivatives.iter()).enumerate() {
+            let interior = i != 0 && i != keys.len() - 1;
+            if let Some(d) = d {
+                assert!((curve.derivative(u, 1) - d).abs() < 1e-10);
+                if interior {
+                    check_continuous(&curve, u, 1);
+                }
+            } else if interior {
+                check_continuous(&curve, u, 2);
+            }
+        }
+    }
+}
+
+#[test]
+fn hermite_mixed_ends() {
+    // A clamped tangent at the start and a natural end
+    let keys = keys();
+    let mut derivatives = vec![None; keys.len()];
+    derivatives[0] = Some(3.0);
+    let curve = BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::Natural).unwrap();
+    check_keys(&curve, &keys);
+    assert!((curve.derivative(0.0, 1) - 3.0).abs() < 1e-10);
+    assert!(curve.derivative(3.0, 2).abs() < 1e-10);
+}
+
+#[test]
+fn periodic() {
+    let mut keys: Vec<(f64, f64)> = (0..=8)
+        .map(|i| {
+            let u = i as f64 / 8.0;
+            (u, (2.0 * std::f64::consts::PI * u).sin())
+        })
+        .collect();
+    // sin(2 pi) isn't exactly zero in floating point
+    keys[8].1 = keys[0].1;
+    let curve = BSpline::interpolate_hermite(&keys, &[], EndCondition::Periodic).unwrap();
+    check_keys(&curve, &keys);
+    for order in 1..3 {
+        assert!((curve.derivative(0.0, order) - curve.derivative(1.0, order)).abs() < 1e-10);
+    }
+
+    let mut derivatives = vec![None; keys.len()];
+    derivatives[8] = Some(5.0);
+    let curve = BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::Periodic).unwrap();
+    check_keys(&curve, &keys);
+    assert!((curve.derivative(0.0, 1) - 5.0).abs() < 1e-10);
+    assert!((curve.derivative(1.0, 1) - 5.0).abs() < 1e-10);
+    assert!((curve.derivative(0.0, 2) - curve.derivative(1.0, 2)).abs() < 1e-10);
+
+    // The same derivative can be given at both ends, but not different ones
+    derivatives[0] = Some(5.0);
+    let both = BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::Periodic).unwrap();
+    assert!((both.point(0.3) - curve.point(0.3)).abs() < 1e-10);
+    derivatives[0] = Some(4.0);
+    assert_eq!(
+        BSpline::interpolate_hermite(&keys, &derivatives, EndCondition::Periodic).unwrap_err(),
+        BSplineError::PeriodicMismatch
+    );
+
+    // The curve must be closed
+    let mut open = keys.clone();
+    open[8].1 = 0.5;
+    assert_eq!(
+        BSpline::interpolate_hermite(&open, &[], EndCondition::Periodic).unwrap_err(),
+        BSplineError::PeriodicMismatch
+    );
+}
+
+#[test]
+fn hermite_errors() {
+    let keys = keys();
+    assert_eq!(
+        BSpline::interpolate_hermite(&keys, &[None, None], EndCondition::Natural).unwrap_err(),
+        BSplineError::InvalidDerivativeCount {
+            expected: 6,
+            found: 2
+        }
+    );
+    let mut unsorted = keys.clone();
+    unsorted.swap(2, 3);
+    assert_eq!(
+        BSpline::interpolate_hermite(&unsorted, &[], EndCondition::Natural).unwrap_err(),
+        BSplineError::UnsortedKnots { index: 3 }
+    );
+    assert_eq!(
+        BSpline::interpolate_hermite(&keys[..3], &[], EndCondition::NotAKnot).unwrap_err(),
+        BSplineError::TooFewControlPoints {
+            degree: 3,
+            found: 3
+        }
+    );
+    // A line between two keys
+    let line = BSpline::interpolate_hermite(&keys[..2], &[], EndCondition::Natural).unwrap();
+    assert!((line.point(0.25) - 1.5).abs() < 1e-12);
+}
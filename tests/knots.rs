@@ -0,0 +1,95 @@
+extern crate bspline;
+use bspline::{BSpline, BSplineError, KnotVector};
+
+fn knots(k: &KnotVector<f64>) -> Vec<f64> {
+    k.knots().cloned().collect()
+}
+
+#[test]
+fn uniform() {
+    let k = KnotVector::<f64>::uniform(2, 4);
+    assert_eq!(knots(&k), vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(k.knot_domain(), (2.0, 4.0));
+    assert_eq!(k.control_point_count(), 4);
+}
+
+#[test]
+fn clamped() {
+    let k = KnotVector::<f64>::clamped(3, 7);
+    assert_eq!(
+        knots(&k),
+        vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 4.0, 4.0, 4.0]
+    );
+    assert_eq!(k.knot_domain(), (0.0, 4.0));
+
+    let k = KnotVector::<f64>::clamped(1, 2);
+    assert_eq!(knots(&k), vec![0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn periodic() {
+    let k = KnotVector::<f64>::periodic(3, 5);
+    assert_eq!(k.control_point_count(), 8);
+    assert_eq!(k.knot_domain(), (0.0, 5.0));
+    assert_eq!(knots(&k)[0], -3.0);
+    assert_eq!(knots(&k)[11], 8.0);
+
+    // A closed curve wrapping the first `degree` control points
+    let points = vec![0.0, 1.0, 3.0, 2.0, -1.0];
+    let mut wrapped = points.clone();
+    wrapped.extend_from_slice(&points[..3]);
+    let curve = BSpline::with_knot_vector(wrapped, k);
+    assert!((curve.point(0.0) - curve.point(5.0)).abs() < 1e-12);
+    for order in 1..3 {
+        let d = curve.derivative(0.0, order) - curve.derivative(5.0, order);
+        assert!(d.abs() < 1e-12);
+    }
+}
+
+#[test]
+fn with_multiplicity() {
+    let k = KnotVector::<f64>::clamped(2, 4)
+        .with_multiplicity(1.0, 2)
+        .with_multiplicity(1.5, 1);
+    assert_eq!(knots(&k), vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.5, 2.0, 2.0, 2.0]);
+    assert_eq!(k.control_point_count(), 6);
+    // The curve passes through the control point at the double knot
+    let curve = BSpline::with_knot_vector(vec![0.0, 1.0, 5.0, 2.0, 3.0, 4.0], k.clone());
+    assert!((curve.point(1.0) - 5.0).abs() < 1e-12);
+
+    let k = k.with_multiplicity(1.0, 1);
+    assert_eq!(knots(&k), vec![0.0, 0.0, 0.0, 1.0, 1.5, 2.0, 2.0, 2.0]);
+}
+
+#[test]
+#[should_panic]
+fn with_multiplicity_too_high() {
+    KnotVector::<f64>::clamped(2, 4).with_multiplicity(1.0, 4);
+}
+
+#[test]
+#[should_panic]
+fn with_multiplicity_zero() {
+    KnotVector::<f64>::clamped(2, 4).with_multiplicity(1.0, 0);
+}
+
+#[test]
+fn rescale() {
+    let k = KnotVector::<f64>::clamped(2, 5).rescale(-1.0, 0.5);
+    assert_eq!(knots(&k), vec![-1.0, -1.0, -1.0, -0.5, 0.0, 0.5, 0.5, 0.5]);
+    let k = KnotVector::<f64>::uniform(1, 3).rescale(0.0, 1.0);
+    assert_eq!(knots(&k), vec![-0.5, 0.0, 0.5, 1.0, 1.5]);
+}
+
+#[test]
+fn try_with_knot_vector() {
+    let k = KnotVector::<f64>::clamped(3, 5);
+    assert_eq!(
+        BSpline::try_with_knot_vector(vec![0.0; 4], k.clone()).unwrap_err(),
+        BSplineError::InvalidKnotCount {
+            expected: 8,
+            found: 9
+        }
+    );
+    assert!(BSpline::try_with_knot_vector(vec![0.0; 5], k).is_ok());
+}
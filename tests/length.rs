@@ -0,0 +1,122 @@
+extern crate bspline;
+mod common;
+use bspline::BSpline;
+use common::Point;
+
+fn cubic() -> BSpline<Point, f64> {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 3.0),
+        Point::new(2.0, -1.0),
+        Point::new(4.0, 2.0),
+        Point::new(5.0, 0.0),
+        Point::new(5.5, 4.0),
+    ];
+    let knots = vec![0.0, 0.0, 0.0, 0.0, 0.3, 2.0, 3.0, 3.0, 3.0, 3.0];
+    BSpline::new(3, points, knots)
+}
+
+/// Approximate the length of the curve over `[t0, t1]` with a fine polyline
+fn polyline_length(curve: &BSpline<Point, f64>, t0: f64, t1: f64) -> f64 {
+    let n = 200_000;
+    let mut length = 0.0;
+    let mut last = curve.point(t0);
+    for i in 1..=n {
+        let p = curve.point(t0 + (t1 - t0) * i as f64 / n as f64);
+        length += p.distance(&last);
+        last = p;
+    }
+    length
+}
+
+#[test]
+fn line() {
+    // A cubic tracing a straight line at an uneven speed
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(0.3, 0.4),
+        Point::new(2.4, 3.2),
+        Point::new(3.0, 4.0),
+    ];
+    let knots = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+    let line = BSpline::new(3, points, knots);
+    assert!((line.arc_length(0.0, 1.0) - 5.0).abs() < 1e-12);
+    assert!((line.arc_length(1.0, 0.0) + 5.0).abs() < 1e-12);
+    assert_eq!(line.arc_length(0.5, 0.5), 0.0);
+    let t = line.param_at_length(2.5);
+    assert!((line.point(t).distance(&Point::new(1.5, 2.0))) < 1e-10);
+    assert_eq!(line.param_at_length(-1.0), 0.0);
+    assert_eq!(line.param_at_length(6.0), 1.0);
+
+    let samples = line.sample_arc_length(11);
+    assert_eq!(samples.len(), 11);
+    for (i, p) in samples.iter().enumerate() {
+        let expected = Point::new(0.3 * i as f64, 0.4 * i as f64);
+        assert!(p.distance(&expected) < 1e-10);
+    }
+}
+
+#[test]
+fn arc_length() {
+    let curve = cubic();
+    let total = curve.arc_length(0.0, 3.0);
+    assert!((total - polyline_length(&curve, 0.0, 3.0)).abs() < 1e-8);
+    let part = curve.arc_length(0.1, 2.5);
+    assert!((part - polyline_length(&curve, 0.1, 2.5)).abs() < 1e-8);
+    // Lengths add up across the knots
+    let split =
+        curve.arc_length(0.0, 0.3) + curve.arc_length(0.3, 2.0) + curve.arc_length(2.0, 3.0);
+    assert!((split - total).abs() < 1e-12);
+}
+
+#[test]
+fn param_at_length() {
+    let curve = cubic();
+    let total = curve.arc_length(0.0, 3.0);
+    for i in 0..=20 {
+        let s = total * i as f64 / 20.0;
+        let t = curve.param_at_length(s);
+        assert!((curve.arc_length(0.0, t) - s).abs() < 1e-9);
+    }
+    assert_eq!(curve.param_at_length(total * 2.0), 3.0);
+}
+
+#[test]
+fn sample_arc_length() {
+    let curve = cubic();
+    let total = curve.arc_length(0.0, 3.0);
+    let n = 50;
+    let samples = curve.sample_arc_length(n);
+    assert_eq!(samples.len(), n);
+    assert!(samples[0].distance(&curve.point(0.0)) < 1e-12);
+    assert!(samples[n - 1].distance(&curve.point(3.0)) < 1e-12);
+    // The chords between the samples are all close to the same length along the curve
+    let step = total / (n - 1) as f64;
+    for w in samples.windows(2) {
+        let chord = w[0].distance(&w[1]);
+        assert!(chord <= step + 1e-9);
+        assert!(chord > 0.9 * step);
+    }
+    assert!(curve.sample_arc_length(0).is_empty());
+    assert_eq!(curve.sample_arc_length(1).len(), 1);
+}
+
+#[test]
+fn scalar() {
+    // For a scalar curve the length is the total variation
+    let curve = BSpline::new(
+        2,
+        vec![0.0, 2.0, -1.0, 1.0],
+        vec![0.0, 0.0, 0.0, 1.0, 2.0, 2.0, 2.0],
+    );
+    let (min, max) = curve.knot_domain();
+    let n = 100_000;
+    let variation: f64 = (1..=n)
+        .map(|i| {
+            let a = min + (max - min) * (i - 1) as f64 / n as f64;
+            let b = min + (max - min) * i as f64 / n as f64;
+            (curve.point(b) - curve.point(a)).abs()
+        })
+        .sum();
+    assert!((curve.arc_length(min, max) - variation).abs() < 1e-6);
+}
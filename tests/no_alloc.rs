@@ -0,0 +1,49 @@
+extern crate bspline;
+use bspline::BSpline;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Allocator that counts the allocations made on each thread so we can check that
+/// evaluating the curve doesn't touch the heap
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|a| a.get())
+}
+
+#[test]
+fn evaluation_does_not_allocate() {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    let spline = BSpline::new(3, points, knots);
+
+    let before = allocations();
+    let mut sum = 0.0;
+    for i in 0..=400 {
+        let t = -2.0 + 0.01 * i as f64;
+        sum += spline.point(t);
+        sum += spline.point_clamped(t);
+        sum += spline.derivative(t, 1);
+        sum += spline.derivative(t, 2);
+    }
+    assert_eq!(allocations(), before);
+    assert!(sum.is_finite());
+}
@@ -0,0 +1,94 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, BSplineError, InnerProduct, NurbsCurve};
+use common::Point;
+
+/// The unit circle as a quadratic NURBS curve made of four quarter circle arcs
+fn unit_circle() -> NurbsCurve<Point, f64> {
+    let points = vec![
+        Point::new(1.0, 0.0),
+        Point::new(1.0, 1.0),
+        Point::new(0.0, 1.0),
+        Point::new(-1.0, 1.0),
+        Point::new(-1.0, 0.0),
+        Point::new(-1.0, -1.0),
+        Point::new(0.0, -1.0),
+        Point::new(1.0, -1.0),
+        Point::new(1.0, 0.0),
+    ];
+    let w = 0.5f64.sqrt();
+    let weights = vec![1.0, w, 1.0, w, 1.0, w, 1.0, w, 1.0];
+    let knots = vec![
+        0.0, 0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0, 1.0,
+    ];
+    NurbsCurve::new(2, points, weights, knots)
+}
+
+#[test]
+fn circle() {
+    let circle = unit_circle();
+    for i in 0..=100 {
+        let t = i as f64 / 100.0;
+        assert!((circle.point(t).norm() - 1.0).abs() < 1e-12);
+    }
+    let p = circle.point(0.25);
+    assert!((p.x - 0.0).abs() < 1e-12 && (p.y - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn circle_derivatives() {
+    let circle = unit_circle();
+    let h = 1e-6;
+    // Skip the double knots between the arcs where the second derivative is discontinuous
+    for i in (1..100).filter(|i| i % 25 != 0) {
+        let t = i as f64 / 100.0;
+        let p = circle.point(t);
+        let d1 = circle.derivative(t, 1);
+        let d2 = circle.derivative(t, 2);
+        // The tangent of a circle is perpendicular to the radius
+        assert!((p.x * d1.x + p.y * d1.y).abs() < 1e-9);
+        let fd1 = (circle.point(t + h) + circle.point(t - h) * -1.0) * (0.5 / h);
+        assert!((fd1 + d1 * -1.0).norm() < 1e-5);
+        let fd2 = (circle.derivative(t + h, 1) + circle.derivative(t - h, 1) * -1.0) * (0.5 / h);
+        assert!((fd2 + d2 * -1.0).norm() < 1e-4 * d2.norm().max(1.0));
+    }
+}
+
+#[test]
+fn from_bspline() {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    let spline = BSpline::new(3, points, knots);
+    let nurbs = NurbsCurve::from(spline.clone());
+    assert_eq!(nurbs.knot_domain(), spline.knot_domain());
+    assert!(nurbs.weights().all(|w| w == 1.0));
+    for i in 0..=40 {
+        let t = -2.0 + 0.1 * i as f64;
+        assert!((nurbs.point(t) - spline.point(t)).abs() < 1e-12);
+        assert!((nurbs.derivative(t, 1) - spline.derivative(t, 1)).abs() < 1e-12);
+        assert!((nurbs.derivative(t, 2) - spline.derivative(t, 2)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn invalid_weights() {
+    let points = vec![0.0f64, 1.0, 2.0];
+    let knots = vec![0.0, 0.0, 0.5, 1.0, 1.0];
+    let err = NurbsCurve::try_new(1, points.clone(), vec![1.0, 1.0], knots.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        BSplineError::InvalidWeightCount {
+            expected: 3,
+            found: 2
+        }
+    );
+    let err = NurbsCurve::try_new(1, points, vec![1.0, 0.0, 1.0], knots).unwrap_err();
+    assert_eq!(err, BSplineError::InvalidWeight { index: 1 });
+}
+
+#[test]
+#[should_panic]
+fn new_rejects_invalid_weights() {
+    let knots = vec![0.0, 0.0, 0.5, 1.0, 1.0];
+    NurbsCurve::new(1, vec![0.0f64, 1.0, 2.0], vec![1.0, -1.0, 1.0], knots);
+}
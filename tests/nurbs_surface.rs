@@ -0,0 +1,176 @@
+extern crate bspline;
+mod common;
+use bspline::{BSplineError, BSplineSurface, InnerProduct, NurbsSurface};
+use common::Point3;
+
+/// The unit sphere as the surface of revolution of a half circle about the z axis, with the
+/// full circle of revolution in `u` and the half circle from the south to the north pole in
+/// `v`.
+fn unit_sphere() -> NurbsSurface<Point3, f64> {
+    let w = 0.5f64.sqrt();
+    let circle = [
+        (1.0, 0.0, 1.0),
+        (1.0, 1.0, w),
+        (0.0, 1.0, 1.0),
+        (-1.0, 1.0, w),
+        (-1.0, 0.0, 1.0),
+        (-1.0, -1.0, w),
+        (0.0, -1.0, 1.0),
+        (1.0, -1.0, w),
+        (1.0, 0.0, 1.0),
+    ];
+    let profile = [
+        (0.0, -1.0, 1.0),
+        (1.0, -1.0, w),
+        (1.0, 0.0, 1.0),
+        (1.0, 1.0, w),
+        (0.0, 1.0, 1.0),
+    ];
+    let points = circle
+        .iter()
+        .map(|&(x, y, _)| {
+            profile
+                .iter()
+                .map(|&(r, z, _)| Point3::new(r * x, r * y, z))
+                .collect()
+        })
+        .collect();
+    let weights = circle
+        .iter()
+        .map(|&(_, _, a)| profile.iter().map(|&(_, _, b)| a * b).collect())
+        .collect();
+    let knots_u = vec![
+        0.0, 0.0, 0.0, 0.25, 0.25, 0.5, 0.5, 0.75, 0.75, 1.0, 1.0, 1.0,
+    ];
+    let knots_v = vec![0.0, 0.0, 0.0, 0.5, 0.5, 1.0, 1.0, 1.0];
+    NurbsSurface::new(2, 2, points, weights, knots_u, knots_v)
+}
+
+#[test]
+fn sphere() {
+    let sphere = unit_sphere();
+    assert_eq!(sphere.knot_domain(), ((0.0, 1.0), (0.0, 1.0)));
+    assert_eq!(sphere.control_point_count(), (9, 5));
+    assert_eq!(sphere.degree_u(), 2);
+    assert_eq!(sphere.degree_v(), 2);
+    for i in 0..=40 {
+        let u = i as f64 / 40.0;
+        for j in 0..=40 {
+            let v = j as f64 / 40.0;
+            let p = sphere.point(u, v);
+            assert!((p.norm() - 1.0).abs() < 1e-12);
+        }
+    }
+    let south = sphere.point(0.3, 0.0);
+    assert!(south.distance(&Point3::new(0.0, 0.0, -1.0)) < 1e-12);
+    let equator = sphere.point(0.25, 0.5);
+    assert!(equator.distance(&Point3::new(0.0, 1.0, 0.0)) < 1e-12);
+}
+
+#[test]
+fn normals() {
+    let sphere = unit_sphere();
+    for i in 0..=20 {
+        let u = i as f64 / 20.0;
+        for j in 1..20 {
+            let v = j as f64 / 20.0;
+            // The outward normal of the unit sphere is the point itself
+            let n = sphere.normal(u, v);
+            assert!(n.distance(&sphere.point(u, v)) < 1e-12);
+        }
+    }
+    // The partial derivative in u vanishes at the poles
+    assert_eq!(sphere.normal(0.5, 1.0), Point3::new(0.0, 0.0, 0.0));
+}
+
+#[test]
+fn derivatives() {
+    let sphere = unit_sphere();
+    let h = 1e-5;
+    for &(u, v) in &[(0.1, 0.2), (0.4, 0.7), (0.8, 0.45), (0.6, 0.9)] {
+        let p = |u, v| sphere.point(u, v);
+        let du = (p(u + h, v) + p(u - h, v) * -1.0) * (0.5 / h);
+        let dv = (p(u, v + h) + p(u, v - h) * -1.0) * (0.5 / h);
+        assert!(sphere.derivative(u, v, 1, 0).distance(&du) < 1e-6);
+        assert!(sphere.derivative(u, v, 0, 1).distance(&dv) < 1e-6);
+
+        let d = |u, v| sphere.derivative(u, v, 1, 0);
+        let duu = (d(u + h, v) + d(u - h, v) * -1.0) * (0.5 / h);
+        let duv = (d(u, v + h) + d(u, v - h) * -1.0) * (0.5 / h);
+        assert!(sphere.derivative(u, v, 2, 0).distance(&duu) < 1e-4);
+        assert!(sphere.derivative(u, v, 1, 1).distance(&duv) < 1e-4);
+        assert_eq!(sphere.derivative(u, v, 0, 0), sphere.point(u, v));
+    }
+}
+
+#[test]
+fn from_bspline() {
+    let grid = vec![
+        vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 1.0)],
+        vec![Point3::new(1.0, 0.0, 0.5), Point3::new(1.0, 1.0, 0.0)],
+        vec![Point3::new(2.0, 0.0, 1.0), Point3::new(2.0, 1.0, 2.0)],
+    ];
+    let knots_u = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+    let knots_v = vec![0.0, 0.0, 1.0, 1.0];
+    let surface = BSplineSurface::new(2, 1, grid, knots_u, knots_v);
+    let nurbs = NurbsSurface::from(surface.clone());
+    assert!(nurbs.weights().all(|w| w == 1.0));
+    for &(u, v) in &[(0.0, 0.0), (0.3, 0.6), (1.0, 0.5), (0.75, 1.0)] {
+        assert!(nurbs.point(u, v).distance(&surface.point(u, v)) < 1e-12);
+        let n = nurbs.normal(u, v);
+        assert!(n.distance(&surface.normal(u, v)) < 1e-12);
+        assert!((n.norm() - 1.0).abs() < 1e-12);
+        assert!(n.dot(&surface.derivative(u, v, 1, 0)).abs() < 1e-12);
+        assert!(n.dot(&surface.derivative(u, v, 0, 1)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn try_new() {
+    let knots = vec![0.0, 0.0, 1.0, 1.0];
+    let grid = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+    let err = NurbsSurface::try_new(
+        1,
+        1,
+        grid.clone(),
+        vec![vec![1.0, 1.0], vec![1.0]],
+        knots.clone(),
+        knots.clone(),
+    );
+    assert_eq!(
+        err.unwrap_err(),
+        BSplineError::InvalidWeightCount {
+            expected: 4,
+            found: 3
+        }
+    );
+    let err = NurbsSurface::try_new(
+        1,
+        1,
+        grid.clone(),
+        vec![vec![1.0, 1.0], vec![1.0, -1.0]],
+        knots.clone(),
+        knots.clone(),
+    );
+    assert_eq!(err.unwrap_err(), BSplineError::InvalidWeight { index: 3 });
+    let surface = NurbsSurface::try_new(
+        1,
+        1,
+        grid,
+        vec![vec![1.0, 1.0], vec![1.0, 3.0]],
+        knots.clone(),
+        knots,
+    )
+    .unwrap();
+    assert_eq!(surface.point(1.0, 1.0), 4.0);
+    assert_eq!(surface.point(0.5, 1.0), 3.5);
+}
+
+#[test]
+#[should_panic]
+fn new_rejects_invalid_weights() {
+    let knots = vec![0.0, 0.0, 1.0, 1.0];
+    let grid = vec![vec![1.0f64, 2.0], vec![3.0, 4.0]];
+    let weights = vec![vec![1.0, 0.0], vec![1.0, 1.0]];
+    NurbsSurface::new(1, 1, grid, weights, knots.clone(), knots);
+}
@@ -0,0 +1,88 @@
+extern crate bspline;
+use bspline::{BSplineError, PeriodicBSpline};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+/// Check the curve and its derivatives up to `degree - 1` match across the seam
+fn check_seam(curve: &PeriodicBSpline<f64, f64>) {
+    let (min, max) = curve.knot_domain();
+    for order in 0..curve.degree() {
+        let a = curve.as_bspline().derivative(min, order);
+        let b = curve.as_bspline().derivative(max, order);
+        assert!(close(a, b), "order {} differs, {} != {}", order, a, b);
+    }
+}
+
+#[test]
+fn uniform() {
+    let points = vec![0.0, 1.0, 3.0, -1.0, 2.0, 0.5];
+    for degree in 1..=4 {
+        let curve = PeriodicBSpline::new(degree, points.clone());
+        assert_eq!(curve.degree(), degree);
+        assert_eq!(curve.knot_domain(), (0.0, 6.0));
+        assert_eq!(curve.period(), 6.0);
+        assert_eq!(curve.control_points(), &points[..]);
+        check_seam(&curve);
+        // The parameter wraps around in both directions
+        for i in 0..=60 {
+            let t = i as f64 / 10.0;
+            let p = curve.point(t);
+            assert!(close(p, curve.point(t + 6.0)));
+            assert!(close(p, curve.point(t - 18.0)));
+            assert!(close(curve.derivative(t, 1), curve.derivative(t + 12.0, 1)));
+        }
+    }
+}
+
+#[test]
+fn with_knots() {
+    let points = vec![0.0, 1.0, 3.0, -1.0, 2.0];
+    let knots = vec![1.0, 1.5, 3.0, 3.5, 5.0, 6.0];
+    let curve = PeriodicBSpline::with_knots(3, points.clone(), knots).unwrap();
+    assert_eq!(curve.knot_domain(), (1.0, 6.0));
+    assert_eq!(curve.control_points(), &points[..]);
+    check_seam(&curve);
+    assert!(close(curve.point(0.5), curve.point(5.5)));
+
+    // A double knot lowers the continuity at the seam
+    let knots = vec![0.0, 0.0, 1.0, 2.0, 3.0, 4.0];
+    let curve = PeriodicBSpline::with_knots(3, points, knots).unwrap();
+    let b = curve.as_bspline();
+    assert!(close(b.point(0.0), b.point(4.0)));
+    assert!(close(b.derivative(0.0, 1), b.derivative(4.0, 1)));
+}
+
+#[test]
+fn with_knots_errors() {
+    let points = vec![0.0, 1.0, 3.0, -1.0, 2.0];
+    assert_eq!(
+        PeriodicBSpline::with_knots(3, points.clone(), vec![0.0, 1.0, 2.0]).unwrap_err(),
+        BSplineError::InvalidKnotCount {
+            expected: 6,
+            found: 3
+        }
+    );
+    assert_eq!(
+        PeriodicBSpline::with_knots(3, points[..3].to_vec(), vec![0.0, 1.0, 2.0, 3.0]).unwrap_err(),
+        BSplineError::TooFewControlPoints {
+            degree: 3,
+            found: 3
+        }
+    );
+    assert_eq!(
+        PeriodicBSpline::with_knots(2, points, vec![0.0, 1.0, 3.0, 2.0, 4.0, 5.0]).unwrap_err(),
+        BSplineError::UnsortedKnots { index: 3 }
+    );
+}
+
+#[test]
+fn sample() {
+    let curve = PeriodicBSpline::new(2, vec![1.0, 2.0, 3.0, 4.0]);
+    let samples = curve.sample(8);
+    assert_eq!(samples.len(), 8);
+    for (i, p) in samples.iter().enumerate() {
+        assert!(close(*p, curve.point(i as f64 / 2.0)));
+    }
+}
@@ -0,0 +1,194 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, InnerProduct};
+use common::Point;
+
+/// A wiggly cubic with several points locally closest to most queries
+fn cubic() -> BSpline<Point, f64> {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 3.0),
+        Point::new(2.0, -2.0),
+        Point::new(3.0, 3.0),
+        Point::new(4.0, -2.0),
+        Point::new(5.0, 3.0),
+        Point::new(6.0, 0.0),
+    ];
+    let knots = vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 2.5, 3.0, 3.0, 3.0, 3.0];
+    BSpline::new(3, points, knots)
+}
+
+/// Find the closest point by sampling the curve and refining the closest sample with a
+/// golden section search between its neighbours
+fn brute_force(curve: &BSpline<Point, f64>, query: Point) -> (f64, f64) {
+    let (min, max) = curve.knot_domain();
+    let n = 2_000;
+    let step = (max - min) / n as f64;
+    let distance = |t: f64| curve.point(t).distance(&query);
+    let (best, _) = (0..=n)
+        .map(|i| (min + step * i as f64, distance(min + step * i as f64)))
+        .fold(
+            (min, f64::MAX),
+            |best, x| if x.1 < best.1 { x } else { best },
+        );
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = ((best - step).max(min), (best + step).min(max));
+    for _ in 0..60 {
+        let c = b - ratio * (b - a);
+        let d = a + ratio * (b - a);
+        if distance(c) < distance(d) {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+    [(best, distance(best)), (a, distance(a)), (b, distance(b))]
+        .iter()
+        .fold(
+            (min, f64::MAX),
+            |best, &x| if x.1 < best.1 { x } else { best },
+        )
+}
+
+#[test]
+fn on_curve() {
+    let curve = cubic();
+    for i in 0..=30 {
+        let t = 3.0 * i as f64 / 30.0;
+        let (u, p) = curve.closest_point(curve.point(t));
+        assert!((u - t).abs() < 1e-8);
+        assert!(p.distance(&curve.point(t)) < 1e-12);
+    }
+}
+
+#[test]
+fn global_minimum() {
+    let curve = cubic();
+    for i in 0..=8 {
+        for j in 0..=6 {
+            let query = Point::new(-1.0 + 8.0 * i as f64 / 8.0, -4.0 + 10.0 * j as f64 / 6.0);
+            let (t, p) = curve.closest_point(query);
+            assert!(p.distance(&curve.point(t)) < 1e-12);
+            let (_, distance) = brute_force(&curve, query);
+            let found = p.distance(&query);
+            // The sampled distance is an upper bound on the true distance and the search
+            // should find the true closest point, at most a hair further than the refined
+            // sample
+            assert!(found <= distance + 1e-12);
+            assert!(found > distance - 1e-6);
+            // Unless it's an end of the curve the closest point is perpendicular to it
+            if t > 0.0 && t < 3.0 {
+                let tangent = curve.derivative(t, 1);
+                let offset = query + p * -1.0;
+                assert!(offset.dot(&tangent).abs() < 1e-8 * tangent.norm());
+            }
+        }
+    }
+}
+
+#[test]
+fn ends() {
+    let curve = cubic();
+    let (t, p) = curve.closest_point(Point::new(-3.0, -1.0));
+    assert_eq!(t, 0.0);
+    assert!(p.distance(&Point::new(0.0, 0.0)) < 1e-12);
+    let (t, p) = curve.closest_point(Point::new(9.0, 0.5));
+    assert_eq!(t, 3.0);
+    assert!(p.distance(&Point::new(6.0, 0.0)) < 1e-12);
+}
+
+#[test]
+fn symmetric() {
+    // Queries on the axis of symmetry have a root of the perpendicular condition exactly at
+    // the middle of the curve, where the search splits the curve in half
+    let arch = BSpline::new(
+        3,
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+    );
+    let wave = BSpline::new(
+        4,
+        vec![
+            Point::new(0.0, 2.0),
+            Point::new(0.5, -1.0),
+            Point::new(1.0, 3.0),
+            Point::new(1.5, -1.0),
+            Point::new(2.0, 2.0),
+        ],
+        vec![0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0],
+    );
+    let cases = [
+        (&arch, Point::new(1.0, 2.25)),
+        (&arch, Point::new(1.0, 3.0)),
+        (&wave, Point::new(1.0, 1.125)),
+    ];
+    for &(curve, query) in cases.iter() {
+        let (t, p) = curve.closest_point(query);
+        assert!((t - 0.5).abs() < 1e-12);
+        let (_, distance) = brute_force(curve, query);
+        assert!(p.distance(&query) <= distance + 1e-12);
+    }
+}
+
+#[test]
+fn polyline() {
+    let points = vec![
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(2.0, 2.0),
+    ];
+    let curve = BSpline::new(1, points, vec![0.0, 0.0, 1.0, 2.0, 2.0]);
+    let (t, p) = curve.closest_point(Point::new(0.5, 1.0));
+    assert!((t - 0.25).abs() < 1e-12);
+    assert!(p.distance(&Point::new(0.5, 0.0)) < 1e-12);
+    // The corner is the closest point from outside of it
+    let (t, _) = curve.closest_point(Point::new(3.0, -1.0));
+    assert!((t - 1.0).abs() < 1e-12);
+    // Equally close points on both sides of the corner give the first of them
+    let (t, p) = curve.closest_point(Point::new(1.0, 1.0));
+    assert!((t - 0.5).abs() < 1e-12);
+    assert!(p.distance(&Point::new(1.0, 0.0)) < 1e-12);
+}
+
+#[test]
+fn discontinuous() {
+    // The knot at 1 is repeated degree + 1 times, so the curve jumps from (2, 0) to (2, 5)
+    let curve = BSpline::new(
+        1,
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 5.0),
+            Point::new(4.0, 5.0),
+        ],
+        vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0],
+    );
+    let (t, p) = curve.closest_point(Point::new(2.5, -1.0));
+    assert_eq!(t, 1.0);
+    assert!(p.distance(&Point::new(2.0, 0.0)) < 1e-12);
+    let (t, p) = curve.closest_point(Point::new(1.5, 6.0));
+    assert_eq!(t, 1.0);
+    assert!(p.distance(&Point::new(2.0, 5.0)) < 1e-12);
+}
+
+#[test]
+fn scalar() {
+    let curve = BSpline::new(
+        2,
+        vec![0.0f64, 4.0, -2.0, 1.0],
+        vec![0.0, 0.0, 0.0, 1.0, 2.0, 2.0, 2.0],
+    );
+    // The closest point to a value the curve reaches is where it first reaches it
+    let (t, p) = curve.closest_point(0.5);
+    assert!((p - 0.5).abs() < 1e-12);
+    assert!(t > 0.0 && t < 1.0);
+    // Values above the curve are closest to its maximum
+    let (t, p) = curve.closest_point(10.0);
+    assert!(curve.derivative(t, 1).abs() < 1e-8);
+    assert!((p - curve.point(t)).abs() < 1e-12);
+}
@@ -0,0 +1,99 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, BSplineError};
+use common::check_same_curve;
+
+fn cubic_spline() -> BSpline<f64, f64> {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    BSpline::new(3, points, knots)
+}
+
+/// A cubic curve whose knot vector is not clamped at the ends
+fn unclamped_spline() -> BSpline<f64, f64> {
+    let points = vec![-1.5, 1.5, -1.5, 1.5];
+    let knots = vec![0.0, 1.0, 2.0, 2.0, 5.0, 5.0, 6.0, 7.0];
+    BSpline::new(3, points, knots)
+}
+
+#[test]
+fn insert_knot() {
+    let spline = cubic_spline();
+    for &(u, times) in [(0.5, 1), (0.5, 3), (0.0, 1), (0.0, 3), (-2.0, 0), (2.0, 0)].iter() {
+        let refined = spline.insert_knot(u, times).unwrap();
+        assert_eq!(refined.control_points().len(), 7 + times);
+        assert!(refined.knots().filter(|&&k| k == u).count() >= times);
+        check_same_curve(&spline, &refined, 1e-12);
+    }
+    // At full multiplicity an interior knot interpolates a control point
+    let refined = spline.insert_knot(0.5, 3).unwrap();
+    assert!(refined
+        .control_points()
+        .any(|p| (p - spline.point(0.5)).abs() < 1e-12));
+
+    let spline = unclamped_spline();
+    for &(u, times) in [(2.0, 2), (3.0, 4), (5.0, 2), (4.5, 1)].iter() {
+        let refined = spline.insert_knot(u, times).unwrap();
+        check_same_curve(&spline, &refined, 1e-12);
+    }
+}
+
+#[test]
+fn insert_knot_errors() {
+    let spline = cubic_spline();
+    assert_eq!(
+        spline.insert_knot(2.5, 1).unwrap_err(),
+        BSplineError::ParameterOutOfDomain
+    );
+    assert_eq!(
+        spline.insert_knot(f64::NAN, 1).unwrap_err(),
+        BSplineError::ParameterOutOfDomain
+    );
+    assert_eq!(
+        spline.insert_knot(0.0, 4).unwrap_err(),
+        BSplineError::KnotMultiplicityTooHigh {
+            index: 5,
+            multiplicity: 5,
+            max: 4
+        }
+    );
+}
+
+#[test]
+fn refine_knots() {
+    let spline = cubic_spline();
+    let new_knots = [-1.5, -1.0, 0.25, 0.5, 0.5, 1.75];
+    let refined = spline.refine_knots(&new_knots).unwrap();
+    check_same_curve(&spline, &refined, 1e-12);
+
+    let mut expect = spline.clone();
+    for &u in new_knots.iter() {
+        expect = expect.insert_knot(u, 1).unwrap();
+    }
+    assert!(refined.knots().eq(expect.knots()));
+    assert!(refined
+        .control_points()
+        .zip(expect.control_points())
+        .all(|(a, b)| (a - b).abs() < 1e-12));
+
+    let spline = unclamped_spline();
+    let refined = spline.refine_knots(&[2.0, 3.0, 3.0, 4.0, 5.0]).unwrap();
+    check_same_curve(&spline, &refined, 1e-12);
+}
+
+#[test]
+fn refine_knots_errors() {
+    let spline = cubic_spline();
+    assert_eq!(
+        spline.refine_knots(&[0.5, 0.25]).unwrap_err(),
+        BSplineError::UnsortedKnots { index: 1 }
+    );
+    assert_eq!(
+        spline.refine_knots(&[-3.0, 0.25]).unwrap_err(),
+        BSplineError::ParameterOutOfDomain
+    );
+    assert!(matches!(
+        spline.refine_knots(&[1.0, 1.0, 1.0, 1.0]).unwrap_err(),
+        BSplineError::KnotMultiplicityTooHigh { .. }
+    ));
+}
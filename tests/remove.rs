@@ -0,0 +1,138 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, BSplineError};
+use common::check_same_curve;
+
+fn cubic() -> BSpline<f64, f64> {
+    BSpline::new(
+        3,
+        vec![0.0, 2.0, -1.0, 3.0, 1.0, 0.5, -2.0],
+        vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 2.5, 4.0, 4.0, 4.0, 4.0],
+    )
+}
+
+#[test]
+fn remove_inserted_knot() {
+    let curve = cubic();
+    for &(u, times) in &[(0.5, 1), (1.0, 1), (1.0, 2), (3.0, 3), (2.25, 2)] {
+        let refined = curve.insert_knot(u, times).unwrap();
+        let (removed, deviation) = refined.remove_knot(u, times, 1e-10).unwrap();
+        assert!(deviation < 1e-10);
+        assert_eq!(removed.knots().len(), curve.knots().len());
+        assert_eq!(removed.control_points().len(), curve.control_points().len());
+        check_same_curve(&curve, &removed, 1e-10);
+    }
+}
+
+#[test]
+fn remove_knot_tolerance() {
+    let curve = cubic();
+    assert_eq!(
+        curve.remove_knot(2.0, 1, 1e-6).unwrap_err(),
+        BSplineError::ToleranceExceeded
+    );
+    let (removed, deviation) = curve.remove_knot(2.0, 1, 10.0).unwrap();
+    assert!(deviation > 1e-6);
+    assert_eq!(removed.control_points().len(), 6);
+    check_same_curve(&curve, &removed, deviation);
+}
+
+#[test]
+fn remove_knot_errors() {
+    let curve = cubic();
+    assert_eq!(
+        curve.remove_knot(0.0, 1, 1.0).unwrap_err(),
+        BSplineError::ParameterOutOfDomain
+    );
+    assert_eq!(
+        curve.remove_knot(1.0, 2, 1.0).unwrap_err(),
+        BSplineError::KnotMultiplicityTooLow {
+            multiplicity: 1,
+            times: 2
+        }
+    );
+    assert_eq!(
+        curve.remove_knot(1.5, 1, 1.0).unwrap_err(),
+        BSplineError::KnotMultiplicityTooLow {
+            multiplicity: 0,
+            times: 1
+        }
+    );
+}
+
+#[test]
+fn simplify_refined() {
+    let curve = cubic();
+    let new_knots: Vec<f64> = (1..40).map(|i| i as f64 / 10.0).collect();
+    let refined = curve.refine_knots(&new_knots).unwrap();
+    let (simplified, deviation) = refined.simplify(1e-10);
+    assert!(deviation < 1e-10);
+    assert_eq!(
+        simplified.knots().collect::<Vec<_>>(),
+        curve.knots().collect::<Vec<_>>()
+    );
+    check_same_curve(&curve, &simplified, 1e-10);
+
+    let (simplified, deviation) = refined.simplify(2.0);
+    assert!(deviation <= 2.0);
+    assert!(simplified.control_points().len() < curve.control_points().len());
+    check_same_curve(&refined, &simplified, deviation);
+}
+
+#[test]
+fn simplify_reduced() {
+    let curve = cubic().elevate_degree(1);
+    let (reduced, _) = curve.reduce_degree(1e-10).unwrap();
+    let (simplified, deviation) = reduced.simplify(1e-10);
+    assert!(deviation < 1e-10);
+    assert_eq!(
+        simplified.control_points().len(),
+        cubic().control_points().len()
+    );
+    check_same_curve(&cubic(), &simplified, 1e-10);
+}
+
+#[test]
+fn remove_full_multiplicity_knot() {
+    let curve = BSpline::new(
+        1,
+        vec![0.0, 1.0, 3.0, 2.0],
+        vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0],
+    );
+    let (removed, deviation) = curve.remove_knot(1.0, 2, 10.0).unwrap();
+    assert_eq!(
+        removed.knots().collect::<Vec<_>>(),
+        vec![&0.0, &0.0, &2.0, &2.0]
+    );
+    assert_eq!(
+        removed.control_points().collect::<Vec<_>>(),
+        vec![&0.0, &2.0]
+    );
+    check_same_curve(&curve, &removed, deviation);
+    assert_eq!(
+        curve.remove_knot(1.0, 2, 0.1).unwrap_err(),
+        BSplineError::ToleranceExceeded
+    );
+}
+
+#[test]
+fn remove_knot_degree_zero() {
+    let curve = BSpline::new(0, vec![1.0, 1.0, 3.0], vec![0.0, 1.0, 2.0, 3.0]);
+    let (removed, deviation) = curve.remove_knot(1.0, 1, 1e-10).unwrap();
+    assert!(deviation < 1e-10);
+    assert_eq!(removed.control_points().len(), 2);
+    assert_eq!(
+        curve.remove_knot(2.0, 1, 1.0).unwrap_err(),
+        BSplineError::ToleranceExceeded
+    );
+
+    let (simplified, deviation) = curve.simplify(1e-10);
+    assert!(deviation < 1e-10);
+    assert_eq!(
+        simplified.knots().collect::<Vec<_>>(),
+        vec![&0.0, &2.0, &3.0]
+    );
+    let (simplified, deviation) = curve.simplify(10.0);
+    assert!(deviation <= 10.0);
+    assert_eq!(simplified.control_points().len(), 1);
+}
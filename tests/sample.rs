@@ -0,0 +1,76 @@
+extern crate bspline;
+use bspline::BSpline;
+
+fn cubic_spline() -> BSpline<f64, f64> {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    BSpline::new(3, points, knots)
+}
+
+#[test]
+fn sample() {
+    let spline = cubic_spline();
+    let points = spline.sample(9);
+    assert_eq!(points.len(), 9);
+    for (i, p) in points.iter().enumerate() {
+        let t = -2.0 + 0.5 * i as f64;
+        assert_eq!(*p, spline.point(t));
+    }
+    assert!(spline.sample(0).is_empty());
+    assert_eq!(spline.sample(1), vec![spline.point(-2.0)]);
+}
+
+#[test]
+fn sample_iter() {
+    let spline = cubic_spline();
+    let samples: Vec<(f64, f64)> = spline.sample_iter(0.1).collect();
+    assert_eq!(samples.len(), 41);
+    assert_eq!(spline.sample_iter(0.1).len(), 41);
+    for &(t, p) in samples.iter() {
+        assert_eq!(p, spline.point(t));
+    }
+    assert!(samples.last().unwrap().0 <= 2.0);
+
+    // Steps that aren't finite and positive only sample the start of the curve
+    for &step in &[f64::NAN, f64::INFINITY, 0.0, -0.1] {
+        let samples: Vec<(f64, f64)> = spline.sample_iter(step).collect();
+        assert_eq!(samples, vec![(-2.0, spline.point(-2.0))]);
+    }
+}
+
+#[test]
+fn evaluate_many() {
+    let spline = cubic_spline();
+    // Sorted parameters, including repeated values and the domain end
+    let ts = vec![-2.0, -1.5, -1.0, -1.0, -0.6, 0.0, 0.5, 1.5, 2.0];
+    let mut points = vec![0.0; ts.len()];
+    spline.evaluate_many(&ts, &mut points);
+    for (t, p) in ts.iter().zip(points.iter()) {
+        assert_eq!(*p, spline.point(*t));
+    }
+    // Unsorted parameters fall back to searching for the knot span
+    let ts = vec![1.5, -1.5, 0.5, -2.0, 2.0, -0.6];
+    let mut points = vec![0.0; ts.len()];
+    spline.evaluate_many(&ts, &mut points);
+    for (t, p) in ts.iter().zip(points.iter()) {
+        assert_eq!(*p, spline.point(*t));
+    }
+}
+
+#[test]
+fn sample_iter_matches_stepping() {
+    // The same parameters as stepping through the domain by hand, like the 2D plot tests do
+    let points: Vec<f32> = vec![0.0, 1.0, -1.0, 0.5, 2.0];
+    let knots: Vec<f32> = vec![0.0, 0.0, 0.0, 0.0, 1.5, 3.0, 3.0, 3.0, 3.0];
+    let spline = BSpline::new(3, points, knots);
+    let step_size = 0.001;
+    let t_range = spline.knot_domain();
+    let steps = ((t_range.1 - t_range.0) / step_size) as usize;
+    let samples: Vec<(f32, f32)> = spline.sample_iter(step_size).collect();
+    assert_eq!(samples.len(), steps + 1);
+    for (s, &(t, p)) in samples.iter().enumerate() {
+        let expect = (step_size * s as f32 + t_range.0).min(t_range.1);
+        assert_eq!(t, expect);
+        assert_eq!(p, spline.point(t));
+    }
+}
@@ -0,0 +1,153 @@
+extern crate bspline;
+use bspline::{BSpline, BSplineError, FitOptions, Smoothing};
+
+fn noisy_samples() -> Vec<(f64, f64)> {
+    (0..=60)
+        .map(|i| {
+            let t = i as f64 / 20.0;
+            (
+                t,
+                t.sin() + 0.1 * (37.0 * t).sin() + 0.05 * (91.0 * t).cos(),
+            )
+        })
+        .collect()
+}
+
+/// Compute the smoothing objective of the curve, the squared error plus lambda times the
+/// integrated squared second derivative using Simpson's rule
+fn objective(curve: &BSpline<f64, f64>, samples: &[(f64, f64)], lambda: f64) -> f64 {
+    let error: f64 = samples
+        .iter()
+        .map(|&(t, y)| (curve.point(t) - y).powi(2))
+        .sum();
+    let (min, max) = curve.knot_domain();
+    let n = 3000;
+    let h = (max - min) / n as f64;
+    let mut roughness = 0.0;
+    for i in 0..=n {
+        let w = if i == 0 || i == n {
+            1.0
+        } else if i % 2 == 1 {
+            4.0
+        } else {
+            2.0
+        };
+        roughness += w * curve.derivative(min + i as f64 * h, 2).powi(2);
+    }
+    error + lambda * roughness * h / 3.0
+}
+
+#[test]
+fn smoothing_minimizes_objective() {
+    let samples = noisy_samples();
+    let options = FitOptions::new();
+    for &lambda in &[0.0, 1e-3, 0.1] {
+        let (curve, used) =
+            BSpline::fit_smoothing(3, 15, &samples, Smoothing::Fixed(lambda), &options).unwrap();
+        assert_eq!(used, lambda);
+        assert_eq!(curve.knot_domain(), (0.0, 3.0));
+        let best = objective(&curve, &samples, lambda);
+        let knots: Vec<f64> = curve.knots().cloned().collect();
+        for i in 0..15 {
+            for &delta in &[-1e-2, 1e-2] {
+                let mut points: Vec<f64> = curve.control_points().cloned().collect();
+                points[i] += delta;
+                let moved = BSpline::new(3, points, knots.clone());
+                assert!(objective(&moved, &samples, lambda) > best);
+            }
+        }
+    }
+}
+
+#[test]
+fn smoothing_large_lambda() {
+    // With a large penalty the curve tends towards the least squares line
+    let samples = noisy_samples();
+    let (curve, _) =
+        BSpline::fit_smoothing(3, 12, &samples, Smoothing::Fixed(1e9), &FitOptions::new()).unwrap();
+    let n = samples.len() as f64;
+    let mean_t = samples.iter().map(|s| s.0).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|s| s.1).sum::<f64>() / n;
+    let slope = samples
+        .iter()
+        .map(|s| (s.0 - mean_t) * (s.1 - mean_y))
+        .sum::<f64>()
+        / samples.iter().map(|s| (s.0 - mean_t).powi(2)).sum::<f64>();
+    for i in 0..=30 {
+        let t = i as f64 / 10.0;
+        let line = mean_y + slope * (t - mean_t);
+        assert!((curve.point(t) - line).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn smoothing_gcv() {
+    let samples = noisy_samples();
+    let options = FitOptions::new();
+    let error = |curve: &BSpline<f64, f64>| {
+        (0..=300)
+            .map(|i| {
+                let t = i as f64 / 100.0;
+                (curve.point(t) - t.sin()).powi(2)
+            })
+            .sum::<f64>()
+    };
+    // More control points than samples is fine as the penalty keeps the fit smooth
+    for &n in &[30, 80] {
+        let (curve, lambda) = BSpline::fit_smoothing(
+            3,
+            n,
+            &samples,
+            Smoothing::GeneralizedCrossValidation,
+            &options,
+        )
+        .unwrap();
+        assert!(lambda > 0.0);
+        let (rough, _) =
+            BSpline::fit_smoothing(3, 30, &samples, Smoothing::Fixed(0.0), &options).unwrap();
+        assert!(error(&curve) < 0.5 * error(&rough));
+        for i in 0..=30 {
+            let t = i as f64 / 10.0;
+            assert!((curve.point(t) - t.sin()).abs() < 0.1);
+        }
+    }
+}
+
+#[test]
+fn smoothing_options() {
+    let samples = noisy_samples();
+    let options = FitOptions::new()
+        .with_fixed_endpoints(true)
+        .with_weights(vec![2.0; samples.len()]);
+    let (curve, _) = BSpline::fit_smoothing(
+        4,
+        20,
+        &samples,
+        Smoothing::GeneralizedCrossValidation,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(curve.degree(), 4);
+    assert_eq!(curve.point(0.0), samples[0].1);
+    assert_eq!(curve.point(3.0), samples[60].1);
+}
+
+#[test]
+fn smoothing_errors() {
+    let samples = noisy_samples();
+    let options = FitOptions::new();
+    assert_eq!(
+        BSpline::fit_smoothing(1, 10, &samples, Smoothing::Fixed(1.0), &options).unwrap_err(),
+        BSplineError::DegreeTooLow { degree: 1, min: 2 }
+    );
+    assert_eq!(
+        BSpline::fit_smoothing(3, 10, &samples, Smoothing::Fixed(-1.0), &options).unwrap_err(),
+        BSplineError::InvalidSmoothing
+    );
+    let few = &samples[..5];
+    assert_eq!(
+        BSpline::fit_smoothing(3, 10, few, Smoothing::Fixed(0.0), &options).unwrap_err(),
+        BSplineError::SingularSystem
+    );
+    assert!(BSpline::fit_smoothing(3, 10, few, Smoothing::Fixed(1.0), &options).is_ok());
+}
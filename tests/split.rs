@@ -0,0 +1,87 @@
+extern crate bspline;
+use bspline::BSpline;
+
+fn quartic_spline() -> BSpline<f64, f64> {
+    let points = vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let knots = vec![
+        0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 5.0, 5.0,
+    ];
+    BSpline::new(4, points, knots)
+}
+
+/// A cubic curve whose knot vector is not clamped at the ends
+fn unclamped_spline() -> BSpline<f64, f64> {
+    let points = vec![-1.5, 1.5, -1.5, 1.5];
+    let knots = vec![0.0, 1.0, 2.0, 2.0, 5.0, 5.0, 6.0, 7.0];
+    BSpline::new(3, points, knots)
+}
+
+/// Check that `piece` matches `spline` over the domain of `piece`
+fn check_piece(spline: &BSpline<f64, f64>, piece: &BSpline<f64, f64>) {
+    let (min, max) = piece.knot_domain();
+    for i in 0..=100 {
+        let t = min + (max - min) * i as f64 / 100.0;
+        assert!((spline.point(t) - piece.point(t)).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn split() {
+    let spline = quartic_spline();
+    for &t in [0.5, 2.0, 2.7, 4.9].iter() {
+        let (left, right) = spline.split(t);
+        assert_eq!(left.knot_domain(), (0.0, t));
+        assert_eq!(right.knot_domain(), (t, 5.0));
+        assert_eq!(left.degree(), 4);
+        assert_eq!(right.degree(), 4);
+        check_piece(&spline, &left);
+        check_piece(&spline, &right);
+        assert_eq!(left.control_points().last(), right.control_points().next());
+    }
+
+    let spline = unclamped_spline();
+    for &t in [2.5, 4.0].iter() {
+        let (left, right) = spline.split(t);
+        assert_eq!(left.knot_domain(), (2.0, t));
+        assert_eq!(right.knot_domain(), (t, 5.0));
+        check_piece(&spline, &left);
+        check_piece(&spline, &right);
+    }
+}
+
+#[test]
+#[should_panic]
+fn split_at_domain_end() {
+    quartic_spline().split(5.0);
+}
+
+#[test]
+fn subcurve() {
+    let spline = quartic_spline();
+    for &(t0, t1) in [
+        (0.0, 5.0),
+        (0.0, 2.5),
+        (1.2, 5.0),
+        (1.2, 3.0),
+        (2.0, 3.0),
+        (4.0, 4.1),
+    ]
+    .iter()
+    {
+        let piece = spline.subcurve(t0, t1);
+        assert_eq!(piece.knot_domain(), (t0, t1));
+        check_piece(&spline, &piece);
+        // The knot vector is clamped so the ends are the first and last control points
+        let knots: Vec<f64> = piece.knots().cloned().collect();
+        assert!(knots[..5].iter().all(|&k| k == t0));
+        assert!(knots[knots.len() - 5..].iter().all(|&k| k == t1));
+    }
+
+    // Extracting the whole domain of an unclamped curve clamps it
+    let spline = unclamped_spline();
+    let piece = spline.subcurve(2.0, 5.0);
+    assert_eq!(piece.knot_domain(), (2.0, 5.0));
+    check_piece(&spline, &piece);
+    assert!((*piece.control_points().next().unwrap() - spline.point(2.0)).abs() < 1e-12);
+    assert!((*piece.control_points().last().unwrap() - spline.point(5.0)).abs() < 1e-12);
+}
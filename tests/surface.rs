@@ -0,0 +1,148 @@
+extern crate bspline;
+use bspline::{BSpline, BSplineError, BSplineSurface};
+
+fn curve_u() -> BSpline<f64, f64> {
+    let points = vec![0.0, 2.0, -1.0, 3.0, 1.0];
+    let knots = vec![0.0, 0.0, 0.0, 0.0, 0.5, 2.0, 2.0, 2.0, 2.0];
+    BSpline::new(3, points, knots)
+}
+
+fn curve_v() -> BSpline<f64, f64> {
+    let points = vec![1.0, -2.0, 0.5, 4.0];
+    let knots = vec![-1.0, -1.0, -1.0, 0.0, 1.0, 1.0, 1.0];
+    BSpline::new(2, points, knots)
+}
+
+/// A surface whose control points are the sums of the control points of two curves, which
+/// is the sum of the curves since the basis functions in each direction sum to one.
+fn sum_surface() -> BSplineSurface<f64, f64> {
+    let (a, b) = (curve_u(), curve_v());
+    let grid = a
+        .control_points()
+        .map(|x| b.control_points().map(|y| x + y).collect())
+        .collect();
+    BSplineSurface::new(
+        3,
+        2,
+        grid,
+        a.knots().cloned().collect(),
+        b.knots().cloned().collect(),
+    )
+}
+
+#[test]
+fn bilinear() {
+    let grid = vec![vec![0.0, 1.0], vec![2.0, 5.0]];
+    let surface = BSplineSurface::new(
+        1,
+        1,
+        grid,
+        vec![0.0, 0.0, 1.0, 1.0],
+        vec![0.0, 0.0, 1.0, 1.0],
+    );
+    assert_eq!(surface.point(0.0, 0.0), 0.0);
+    assert_eq!(surface.point(1.0, 1.0), 5.0);
+    assert_eq!(surface.point(0.0, 1.0), 1.0);
+    assert_eq!(surface.point(1.0, 0.0), 2.0);
+    assert_eq!(surface.point(0.5, 0.5), 2.0);
+    assert_eq!(surface.point(0.25, 0.5), 1.25);
+}
+
+#[test]
+fn accessors() {
+    let surface = sum_surface();
+    assert_eq!(surface.degree_u(), 3);
+    assert_eq!(surface.degree_v(), 2);
+    assert_eq!(surface.control_point_count(), (5, 4));
+    assert_eq!(*surface.control_point(1, 2), 2.5);
+    assert_eq!(surface.control_points().count(), 20);
+    assert_eq!(surface.knot_domain_u(), (0.0, 2.0));
+    assert_eq!(surface.knot_domain_v(), (-1.0, 1.0));
+}
+
+#[test]
+fn point() {
+    let surface = sum_surface();
+    let (a, b) = (curve_u(), curve_v());
+    for i in 0..=20 {
+        let u = 2.0 * i as f64 / 20.0;
+        for j in 0..=20 {
+            let v = -1.0 + 2.0 * j as f64 / 20.0;
+            let expected = a.point(u) + b.point(v);
+            assert!((surface.point(u, v) - expected).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn derivatives() {
+    let surface = sum_surface();
+    let (a, b) = (curve_u(), curve_v());
+    for i in 0..=10 {
+        let u = 2.0 * i as f64 / 10.0;
+        for j in 0..=10 {
+            let v = -1.0 + 2.0 * j as f64 / 10.0;
+            for k in 1..=4 {
+                let du = surface.derivative(u, v, k, 0);
+                assert!((du - a.derivative(u, k)).abs() < 1e-9);
+                let dv = surface.derivative(u, v, 0, k);
+                assert!((dv - b.derivative(v, k)).abs() < 1e-9);
+                // The cross derivatives of a sum of curves vanish
+                assert!(surface.derivative(u, v, k, 1).abs() < 1e-9);
+            }
+            assert!((surface.derivative(u, v, 0, 0) - surface.point(u, v)).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn cross_derivative() {
+    // The product u * v as a bilinear surface has a constant mixed derivative
+    let grid = vec![vec![0.0, 0.0], vec![0.0, 6.0]];
+    let surface = BSplineSurface::new(
+        1,
+        1,
+        grid,
+        vec![0.0, 0.0, 2.0, 2.0],
+        vec![0.0, 0.0, 3.0, 3.0],
+    );
+    assert_eq!(surface.derivative(0.5, 1.0, 1, 1), 1.0);
+    assert_eq!(surface.derivative(0.5, 1.0, 1, 0), 1.0);
+    assert_eq!(surface.derivative(0.5, 1.0, 0, 1), 0.5);
+    assert_eq!(surface.derivative(0.5, 1.0, 2, 0), 0.0);
+}
+
+#[test]
+fn try_new() {
+    let knots = vec![0.0, 0.0, 1.0, 1.0];
+    let grid = vec![vec![0.0, 1.0], vec![2.0]];
+    let err = BSplineSurface::try_new(1, 1, grid, knots.clone(), knots.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        BSplineError::InvalidGridRow {
+            row: 1,
+            expected: 2,
+            found: 1
+        }
+    );
+
+    let grid = vec![vec![0.0, 1.0], vec![2.0, 3.0]];
+    let err = BSplineSurface::try_new(1, 2, grid.clone(), knots.clone(), knots.clone());
+    assert_eq!(
+        err.unwrap_err(),
+        BSplineError::TooFewControlPoints {
+            degree: 2,
+            found: 2
+        }
+    );
+    let err = BSplineSurface::try_new(1, 1, grid.clone(), knots.clone(), vec![0.0, 1.0, 1.0]);
+    assert_eq!(
+        err.unwrap_err(),
+        BSplineError::InvalidKnotCount {
+            expected: 4,
+            found: 3
+        }
+    );
+    let surface = BSplineSurface::try_new(1, 1, grid, knots.clone(), knots).unwrap();
+    assert_eq!(surface.point(1.0, 1.0), 3.0);
+}
@@ -0,0 +1,53 @@
+extern crate bspline;
+use bspline::{BSpline, BSplineError};
+
+#[test]
+fn valid_curve() {
+    let points = vec![0.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0];
+    let knots = vec![-2.0, -2.0, -2.0, -2.0, -1.0, 0.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    let spline = BSpline::try_new(3, points, knots).unwrap();
+    assert_eq!(spline.point(0.0), 4.0);
+}
+
+#[test]
+fn invalid_counts() {
+    let err = BSpline::try_new(3, vec![0.0f32, 1.0, 2.0], vec![0.0; 7]).unwrap_err();
+    assert_eq!(
+        err,
+        BSplineError::TooFewControlPoints {
+            degree: 3,
+            found: 3
+        }
+    );
+    let err = BSpline::try_new(1, vec![0.0f32, 1.0], vec![0.0, 0.0, 1.0]).unwrap_err();
+    assert_eq!(
+        err,
+        BSplineError::InvalidKnotCount {
+            expected: 4,
+            found: 3
+        }
+    );
+}
+
+#[test]
+fn invalid_knots() {
+    let points = vec![0.0f32, 1.0, 2.0];
+    let err = BSpline::try_new(1, points.clone(), vec![0.0, 0.0, f32::NAN, 1.0, 1.0]).unwrap_err();
+    assert_eq!(err, BSplineError::NonFiniteKnot { index: 2 });
+
+    let err = BSpline::try_new(1, points.clone(), vec![0.0, 0.0, 2.0, 1.0, 1.0]).unwrap_err();
+    assert_eq!(err, BSplineError::UnsortedKnots { index: 3 });
+
+    let err = BSpline::try_new(1, points.clone(), vec![0.0, 0.0, 0.0, 1.0, 1.0]).unwrap_err();
+    assert_eq!(
+        err,
+        BSplineError::KnotMultiplicityTooHigh {
+            index: 0,
+            multiplicity: 3,
+            max: 2
+        }
+    );
+
+    let err = BSpline::try_new(2, points, vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0]).unwrap_err();
+    assert_eq!(err, BSplineError::EmptyKnotDomain);
+}
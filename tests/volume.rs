@@ -0,0 +1,230 @@
+extern crate bspline;
+mod common;
+use bspline::{BSpline, BSplineError, BSplineVolume, FreeFormDeformation};
+use common::Point3;
+
+fn curves() -> [BSpline<f64, f64>; 3] {
+    [
+        BSpline::new(
+            3,
+            vec![0.0, 2.0, -1.0, 3.0, 1.0],
+            vec![0.0, 0.0, 0.0, 0.0, 0.5, 2.0, 2.0, 2.0, 2.0],
+        ),
+        BSpline::new(
+            2,
+            vec![1.0, -2.0, 0.5, 4.0],
+            vec![-1.0, -1.0, -1.0, 0.0, 1.0, 1.0, 1.0],
+        ),
+        BSpline::new(1, vec![3.0, 0.0, 1.0], vec![0.0, 0.0, 1.0, 3.0, 3.0]),
+    ]
+}
+
+/// A volume whose control points are the sums of the control points of three curves, which
+/// is the sum of the curves since the basis functions in each direction sum to one.
+fn sum_volume() -> BSplineVolume<f64, f64> {
+    let [a, b, c] = curves();
+    let lattice = a
+        .control_points()
+        .map(|x| {
+            b.control_points()
+                .map(|y| c.control_points().map(|z| x + y + z).collect())
+                .collect()
+        })
+        .collect();
+    BSplineVolume::new(
+        3,
+        2,
+        1,
+        lattice,
+        a.knots().cloned().collect(),
+        b.knots().cloned().collect(),
+        c.knots().cloned().collect(),
+    )
+}
+
+#[test]
+fn point() {
+    let volume = sum_volume();
+    let [a, b, c] = curves();
+    assert_eq!(volume.degree_u(), 3);
+    assert_eq!(volume.degree_v(), 2);
+    assert_eq!(volume.degree_w(), 1);
+    assert_eq!(volume.control_point_count(), (5, 4, 3));
+    assert_eq!(*volume.control_point(1, 2, 0), 5.5);
+    assert_eq!(volume.knot_domain(), ((0.0, 2.0), (-1.0, 1.0), (0.0, 3.0)));
+    for i in 0..=10 {
+        let u = 2.0 * i as f64 / 10.0;
+        for j in 0..=10 {
+            let v = -1.0 + 2.0 * j as f64 / 10.0;
+            for k in 0..=10 {
+                let w = 3.0 * k as f64 / 10.0;
+                let expected = a.point(u) + b.point(v) + c.point(w);
+                assert!((volume.point(u, v, w) - expected).abs() < 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn trilinear() {
+    let lattice = vec![
+        vec![vec![0.0, 1.0], vec![2.0, 3.0]],
+        vec![vec![4.0, 5.0], vec![6.0, 8.0]],
+    ];
+    let knots = vec![0.0, 0.0, 1.0, 1.0];
+    let mut volume = BSplineVolume::new(1, 1, 1, lattice, knots.clone(), knots.clone(), knots);
+    assert_eq!(volume.point(1.0, 1.0, 1.0), 8.0);
+    assert_eq!(volume.point(1.0, 0.0, 1.0), 5.0);
+    assert_eq!(volume.point(0.5, 0.5, 0.5), 3.625);
+    *volume.control_point_mut(1, 1, 1) = 7.0;
+    assert_eq!(volume.point(0.5, 0.5, 0.5), 3.5);
+}
+
+#[test]
+fn try_new() {
+    let knots = vec![0.0, 0.0, 1.0, 1.0];
+    let lattice = vec![vec![vec![0.0, 1.0], vec![2.0, 3.0]], vec![vec![4.0, 5.0]]];
+    let err = BSplineVolume::try_new(
+        1,
+        1,
+        1,
+        lattice,
+        knots.clone(),
+        knots.clone(),
+        knots.clone(),
+    );
+    assert_eq!(
+        err.unwrap_err(),
+        BSplineError::InvalidLatticeSlab {
+            slab: 1,
+            expected: 2,
+            found: 1
+        }
+    );
+    let lattice = vec![
+        vec![vec![0.0, 1.0], vec![2.0, 3.0]],
+        vec![vec![4.0, 5.0], vec![6.0]],
+    ];
+    let err = BSplineVolume::try_new(
+        1,
+        1,
+        1,
+        lattice,
+        knots.clone(),
+        knots.clone(),
+        knots.clone(),
+    );
+    assert_eq!(
+        err.unwrap_err(),
+        BSplineError::InvalidGridRow {
+            row: 3,
+            expected: 2,
+            found: 1
+        }
+    );
+    let lattice = vec![
+        vec![vec![0.0, 1.0], vec![2.0, 3.0]],
+        vec![vec![4.0, 5.0], vec![6.0, 7.0]],
+    ];
+    let err = BSplineVolume::try_new(
+        1,
+        1,
+        2,
+        lattice.clone(),
+        knots.clone(),
+        knots.clone(),
+        knots.clone(),
+    );
+    assert_eq!(
+        err.unwrap_err(),
+        BSplineError::TooFewControlPoints {
+            degree: 2,
+            found: 2
+        }
+    );
+    let volume =
+        BSplineVolume::try_new(1, 1, 1, lattice, knots.clone(), knots.clone(), knots).unwrap();
+    assert_eq!(volume.point(0.5, 0.5, 0.5), 3.5);
+}
+
+fn lattice() -> FreeFormDeformation<Point3, f64> {
+    FreeFormDeformation::new(
+        Point3::new(-1.0, 0.0, 2.0),
+        [
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(1.0, 3.0, 0.0),
+            Point3::new(0.0, 0.5, 1.0),
+        ],
+        [3, 2, 1],
+        [6, 4, 3],
+    )
+}
+
+fn test_points() -> Vec<Point3> {
+    let mut points = Vec::new();
+    for i in 0..=6 {
+        for j in 0..=6 {
+            for k in 0..=6 {
+                let x = 0.05 + 0.9 * i as f64 / 6.0;
+                let y = 0.05 + 0.9 * j as f64 / 6.0;
+                let z = 0.05 + 0.9 * k as f64 / 6.0;
+                points.push(Point3::new(-1.0 + 2.0 * x + y, 3.0 * y + 0.5 * z, 2.0 + z));
+            }
+        }
+    }
+    points
+}
+
+#[test]
+fn ffd_identity() {
+    let ffd = lattice();
+    let (u, v, w) = ffd.parameters(Point3::new(0.5, 1.75, 2.5));
+    assert!((u - 0.5).abs() < 1e-12);
+    assert!((v - 0.5).abs() < 1e-12);
+    assert!((w - 0.5).abs() < 1e-12);
+    for p in test_points() {
+        assert!(ffd.deform(p).distance(&p) < 1e-12);
+    }
+    let outside = Point3::new(5.0, 5.0, 5.0);
+    assert_eq!(ffd.deform(outside), outside);
+}
+
+#[test]
+fn ffd_translate() {
+    let mut ffd = lattice();
+    let offset = Point3::new(0.25, -1.0, 0.5);
+    for i in 0..6 {
+        for j in 0..4 {
+            for k in 0..3 {
+                let p = ffd.control_point_mut(i, j, k);
+                *p = *p + offset;
+            }
+        }
+    }
+    for p in test_points() {
+        assert!(ffd.deform(p).distance(&(p + offset)) < 1e-12);
+    }
+}
+
+#[test]
+fn ffd_local() {
+    let mut ffd = lattice();
+    let original = *ffd.control_point(1, 1, 1);
+    *ffd.control_point_mut(1, 1, 1) = original + Point3::new(0.0, 0.0, 1.0);
+    // Only points near the moved control point are deformed
+    let mut moved = 0;
+    for p in test_points() {
+        let (u, _, _) = ffd.parameters(p);
+        let d = ffd.deform(p);
+        if u > 2.0 / 3.0 {
+            assert!(d.distance(&p) < 1e-12);
+        } else if d.distance(&p) > 1e-6 {
+            moved += 1;
+            assert!(d.z > p.z);
+        }
+    }
+    assert!(moved > 0);
+    // The faces of the lattice are unchanged, so points on the box stay put
+    let corner = Point3::new(-1.0, 0.0, 2.0);
+    assert!(ffd.deform(corner).distance(&corner) < 1e-12);
+}